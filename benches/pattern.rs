@@ -0,0 +1,75 @@
+// ====================================================================
+// BENCHMARK: PADRÕES DE NOME
+// ====================================================================
+// Compara `Pattern` com o algoritmo recursivo usado antes por `find_by_name`.
+// Execute com `cargo bench --bench pattern`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use archivus::Pattern;
+
+// Algoritmo anterior, mantido aqui apenas como referência de desempenho
+fn recursive_match(text: &str, pattern: &str, text_idx: usize, pattern_idx: usize) -> bool {
+    let pattern_chars: Vec<char> = pattern.chars().collect();
+    let text_chars: Vec<char> = text.chars().collect();
+
+    if pattern_idx == pattern_chars.len() {
+        return text_idx == text_chars.len();
+    }
+
+    match pattern_chars[pattern_idx] {
+        '*' => (text_idx..=text_chars.len()).any(|i| recursive_match(text, pattern, i, pattern_idx + 1)),
+        '?' => text_idx < text_chars.len() && recursive_match(text, pattern, text_idx + 1, pattern_idx + 1),
+        c => {
+            text_idx < text_chars.len()
+                && text_chars[text_idx] == c
+                && recursive_match(text, pattern, text_idx + 1, pattern_idx + 1)
+        }
+    }
+}
+
+// Executa `f` repetidamente por pelo menos `budget` e retorna o tempo médio
+fn measure<F: FnMut() -> bool>(budget: Duration, mut f: F) -> Duration {
+    let start = Instant::now();
+    let mut runs = 0u32;
+    while runs == 0 || start.elapsed() < budget {
+        black_box(f());
+        runs += 1;
+    }
+    start.elapsed() / runs
+}
+
+fn main() {
+    let budget = Duration::from_millis(200);
+    let cases = [
+        ("extensão simples", "*.txt", "relatorio_anual_2024.txt".to_string()),
+        ("interrogações", "rel?torio_????_2024.*", "relatorio_anual_2024.txt".to_string()),
+        ("vários *, nome de 20", "*a*a*a*a*b", "a".repeat(20)),
+        ("vários *, nome de 40", "*a*a*a*a*b", "a".repeat(40)),
+    ];
+
+    println!("{:<24} {:>14} {:>14} {:>10}", "caso", "recursivo", "Pattern", "ganho");
+    for (name, pattern, text) in &cases {
+        let compiled = Pattern::new(pattern);
+        assert_eq!(compiled.matches(text), recursive_match(text, pattern, 0, 0));
+
+        let old = measure(budget, || recursive_match(black_box(text), black_box(pattern), 0, 0));
+        let new = measure(budget, || compiled.matches(black_box(text)));
+        println!(
+            "{:<24} {:>14?} {:>14?} {:>9.1}x",
+            name,
+            old,
+            new,
+            old.as_secs_f64() / new.as_secs_f64()
+        );
+    }
+
+    // Nomes longos, impraticáveis para o algoritmo recursivo
+    let compiled = Pattern::new("*a*a*a*a*a*a*a*a*b");
+    for len in [1_000, 10_000, 100_000] {
+        let text = "a".repeat(len);
+        let elapsed = measure(budget, || compiled.matches(black_box(&text)));
+        println!("{:<24} {:>14} {:>14?}", format!("Pattern, nome de {}", len), "-", elapsed);
+    }
+}
@@ -0,0 +1,474 @@
+// ====================================================================
+// DIFERENÇAS ENTRE ÁRVORES
+// ====================================================================
+// Compara dois diretórios ou snapshots, como `diff -rq`, detectando
+// arquivos renomeados pelo hash do conteúdo
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::{hash, ArchivusError, Digest, FileFilter, FileType, HashAlgorithm, Snapshot, SnapshotEntry};
+
+/// Critério para considerar um arquivo modificado
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CompareMode {
+    /// Tamanho ou data de modificação diferentes
+    #[default]
+    Metadata,
+    /// Tamanho ou hash do conteúdo diferentes
+    Content,
+}
+
+/// Opções de `Archivus::diff`
+#[derive(Debug, Clone)]
+pub struct DiffOptions {
+    /// Critério de comparação dos arquivos presentes nos dois lados
+    pub compare: CompareMode,
+    /// Agrupa um arquivo removido e um adicionado com o mesmo conteúdo em
+    /// uma renomeação
+    pub detect_renames: bool,
+    /// Algoritmo dos hashes calculados nos diretórios; se um dos lados for
+    /// um snapshot com hashes, o algoritmo dele é usado
+    pub algorithm: HashAlgorithm,
+    /// Itens considerados nos lados que são diretórios
+    pub filter: FileFilter,
+}
+
+impl Default for DiffOptions {
+    fn default() -> Self {
+        Self {
+            compare: CompareMode::Metadata,
+            detect_renames: true,
+            algorithm: HashAlgorithm::XxHash64,
+            filter: FileFilter {
+                recursive: true,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// Lado de uma comparação: um diretório, lido na hora, ou um snapshot
+#[derive(Debug, Clone)]
+pub enum DiffSource {
+    Directory(PathBuf),
+    Snapshot(Snapshot),
+}
+
+impl From<Snapshot> for DiffSource {
+    fn from(snapshot: Snapshot) -> Self {
+        DiffSource::Snapshot(snapshot)
+    }
+}
+
+impl From<PathBuf> for DiffSource {
+    fn from(path: PathBuf) -> Self {
+        DiffSource::Directory(path)
+    }
+}
+
+impl From<&PathBuf> for DiffSource {
+    fn from(path: &PathBuf) -> Self {
+        DiffSource::Directory(path.clone())
+    }
+}
+
+impl From<&Path> for DiffSource {
+    fn from(path: &Path) -> Self {
+        DiffSource::Directory(path.to_path_buf())
+    }
+}
+
+impl From<&str> for DiffSource {
+    fn from(path: &str) -> Self {
+        DiffSource::Directory(PathBuf::from(path))
+    }
+}
+
+/// Diferença entre os dois lados
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    /// Presente apenas no lado direito
+    Added(SnapshotEntry),
+    /// Presente apenas no lado esquerdo
+    Removed(SnapshotEntry),
+    /// Presente nos dois lados, com conteúdo (ou destino de link) diferente
+    Modified { left: SnapshotEntry, right: SnapshotEntry },
+    /// Presente nos dois lados, com tipos diferentes
+    TypeChanged { left: SnapshotEntry, right: SnapshotEntry },
+    /// Arquivo removido de um caminho e adicionado em outro, com o mesmo conteúdo
+    Renamed { from: SnapshotEntry, to: SnapshotEntry },
+}
+
+impl Change {
+    /// Caminho relativo da mudança (para renomeações, o caminho original)
+    pub fn path(&self) -> &str {
+        match self {
+            Change::Added(entry) | Change::Removed(entry) => &entry.path,
+            Change::Modified { left, .. } | Change::TypeChanged { left, .. } => &left.path,
+            Change::Renamed { from, .. } => &from.path,
+        }
+    }
+}
+
+/// Resultado de `Archivus::diff`
+///
+/// `Display` escreve uma linha por mudança, no formato de `diff -rq`
+/// (`Only in ...`, `Files ... differ`, `File ... is a ... while file ... is
+/// a ...`), mais `File ... renamed to ...` para renomeações. Como no `diff`,
+/// o conteúdo de um diretório presente em apenas um lado não é listado.
+#[derive(Debug, Clone)]
+pub struct DiffReport {
+    /// Raiz do lado esquerdo
+    pub left_root: PathBuf,
+    /// Raiz do lado direito
+    pub right_root: PathBuf,
+    /// Mudanças, em ordem de caminho
+    pub changes: Vec<Change>,
+    /// Itens iguais nos dois lados
+    pub unchanged: usize,
+}
+
+impl DiffReport {
+    /// Os dois lados são iguais
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl fmt::Display for DiffReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let directories = |added: bool| -> HashSet<&str> {
+            self.changes
+                .iter()
+                .filter_map(|change| match change {
+                    Change::Added(entry) if added => Some(entry),
+                    Change::Removed(entry) if !added => Some(entry),
+                    _ => None,
+                })
+                .filter(|entry| entry.file_type == FileType::Directory)
+                .map(|entry| entry.path.as_str())
+                .collect()
+        };
+        let (added_dirs, removed_dirs) = (directories(true), directories(false));
+        let inside = |dirs: &HashSet<&str>, path: &str| path.match_indices('/').any(|(index, _)| dirs.contains(&path[..index]));
+
+        let (left, right) = (&self.left_root, &self.right_root);
+        for change in &self.changes {
+            match change {
+                Change::Added(entry) if !inside(&added_dirs, &entry.path) => writeln!(f, "{}", only_in(right, &entry.path))?,
+                Change::Removed(entry) if !inside(&removed_dirs, &entry.path) => writeln!(f, "{}", only_in(left, &entry.path))?,
+                Change::Added(_) | Change::Removed(_) => {}
+                Change::Modified { left: entry, .. } => {
+                    let kind = if entry.file_type == FileType::Symlink { "Symbolic links" } else { "Files" };
+                    writeln!(f, "{} {} and {} differ", kind, join(left, &entry.path), join(right, &entry.path))?
+                }
+                Change::TypeChanged { left: old, right: new } => writeln!(
+                    f,
+                    "File {} is a {} while file {} is a {}",
+                    join(left, &old.path),
+                    type_name(old.file_type),
+                    join(right, &new.path),
+                    type_name(new.file_type)
+                )?,
+                Change::Renamed { from, to } => writeln!(f, "File {} renamed to {}", join(left, &from.path), join(right, &to.path))?,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn join(root: &Path, path: &str) -> String {
+    let root = root.display().to_string();
+    if root.ends_with('/') { format!("{}{}", root, path) } else { format!("{}/{}", root, path) }
+}
+
+fn only_in(root: &Path, path: &str) -> String {
+    match path.rsplit_once('/') {
+        Some((parent, name)) => format!("Only in {}: {}", join(root, parent), name),
+        None => format!("Only in {}: {}", root.display(), path),
+    }
+}
+
+// Nomes usados pelo `diff`
+fn type_name(file_type: FileType) -> &'static str {
+    match file_type {
+        FileType::Regular => "regular file",
+        FileType::Directory => "directory",
+        FileType::Symlink => "symbolic link",
+        FileType::Fifo => "fifo",
+        FileType::Socket => "socket",
+        FileType::BlockDevice => "block special file",
+        FileType::CharDevice => "character special file",
+        FileType::Unknown => "unknown file",
+    }
+}
+
+// Lado da comparação; em diretórios (`live`), os hashes são calculados
+// apenas quando necessários e guardados
+struct Side {
+    root: PathBuf,
+    live: bool,
+    digests: HashMap<String, Digest>,
+}
+
+impl Side {
+    fn digest(&mut self, entry: &SnapshotEntry, algorithm: HashAlgorithm) -> Result<Option<Digest>, ArchivusError> {
+        if let Some(digest) = &entry.digest
+            && digest.algorithm() == algorithm
+        {
+            return Ok(Some(digest.clone()));
+        }
+        if !self.live {
+            return Ok(None);
+        }
+        if let Some(digest) = self.digests.get(&entry.path) {
+            return Ok(Some(digest.clone()));
+        }
+        let digest = hash::hash_file(&self.root.join(&entry.path), algorithm)?;
+        self.digests.insert(entry.path.clone(), digest.clone());
+        Ok(Some(digest))
+    }
+
+    fn required_digest(&mut self, entry: &SnapshotEntry, algorithm: HashAlgorithm) -> Result<Digest, ArchivusError> {
+        self.digest(entry, algorithm)?.ok_or_else(|| {
+            ArchivusError::InvalidPath(format!("o snapshot de {} não tem hash {} de '{}'", self.root.display(), algorithm, entry.path))
+        })
+    }
+}
+
+/// Compara dois snapshots; `live` indica que o lado ainda pode ser lido do
+/// disco para calcular hashes
+pub(crate) fn diff(left: Snapshot, left_live: bool, right: Snapshot, right_live: bool, options: &DiffOptions) -> Result<DiffReport, ArchivusError> {
+    let algorithm = [(&left, left_live), (&right, right_live)]
+        .into_iter()
+        .find_map(|(snapshot, live)| if live { None } else { snapshot.algorithm })
+        .unwrap_or(options.algorithm);
+    let mut left_side = Side { root: left.root, live: left_live, digests: HashMap::new() };
+    let mut right_side = Side { root: right.root, live: right_live, digests: HashMap::new() };
+
+    let mut changes = Vec::new();
+    let mut removed = Vec::new();
+    let mut added = Vec::new();
+    let mut unchanged = 0;
+
+    let mut left_entries = left.entries.into_iter().peekable();
+    let mut right_entries = right.entries.into_iter().peekable();
+    loop {
+        let order = match (left_entries.peek(), right_entries.peek()) {
+            (Some(l), Some(r)) => l.path.cmp(&r.path),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => break,
+        };
+        match order {
+            std::cmp::Ordering::Less => removed.extend(left_entries.next()),
+            std::cmp::Ordering::Greater => added.extend(right_entries.next()),
+            std::cmp::Ordering::Equal => {
+                let (l, r) = (left_entries.next().expect("entrada"), right_entries.next().expect("entrada"));
+                if l.file_type != r.file_type {
+                    changes.push(Change::TypeChanged { left: l, right: r });
+                } else if differs(&l, &r, &mut left_side, &mut right_side, options.compare, algorithm)? {
+                    changes.push(Change::Modified { left: l, right: r });
+                } else {
+                    unchanged += 1;
+                }
+            }
+        }
+    }
+
+    if options.detect_renames {
+        let renamed = detect_renames(&removed, &added, &mut left_side, &mut right_side, algorithm)?;
+        let (from, to): (HashSet<usize>, HashSet<usize>) = renamed.iter().copied().unzip();
+        for (from, to) in renamed {
+            changes.push(Change::Renamed { from: removed[from].clone(), to: added[to].clone() });
+        }
+        removed = removed.into_iter().enumerate().filter(|(index, _)| !from.contains(index)).map(|(_, entry)| entry).collect();
+        added = added.into_iter().enumerate().filter(|(index, _)| !to.contains(index)).map(|(_, entry)| entry).collect();
+    }
+    changes.extend(removed.into_iter().map(Change::Removed));
+    changes.extend(added.into_iter().map(Change::Added));
+    changes.sort_by(|a, b| a.path().cmp(b.path()));
+
+    Ok(DiffReport {
+        left_root: left_side.root,
+        right_root: right_side.root,
+        changes,
+        unchanged,
+    })
+}
+
+fn differs(left: &SnapshotEntry, right: &SnapshotEntry, left_side: &mut Side, right_side: &mut Side, compare: CompareMode, algorithm: HashAlgorithm) -> Result<bool, ArchivusError> {
+    match left.file_type {
+        FileType::Regular => {
+            if left.size != right.size {
+                return Ok(true);
+            }
+            match compare {
+                CompareMode::Metadata => Ok(left.modified.is_some() && right.modified.is_some() && left.modified != right.modified),
+                CompareMode::Content => Ok(left_side.required_digest(left, algorithm)? != right_side.required_digest(right, algorithm)?),
+            }
+        }
+        FileType::Symlink => Ok(left.link_target != right.link_target),
+        _ => Ok(false),
+    }
+}
+
+// Pares (removido, adicionado) de arquivos não vazios com o mesmo tamanho e
+// o mesmo hash; arquivos sem hash disponível não são pareados
+fn detect_renames(removed: &[SnapshotEntry], added: &[SnapshotEntry], left_side: &mut Side, right_side: &mut Side, algorithm: HashAlgorithm) -> Result<Vec<(usize, usize)>, ArchivusError> {
+    let mut candidates: HashMap<u64, Vec<usize>> = HashMap::new();
+    for (index, entry) in added.iter().enumerate() {
+        if entry.file_type == FileType::Regular && entry.size > 0 {
+            candidates.entry(entry.size).or_default().push(index);
+        }
+    }
+
+    let mut pairs = Vec::new();
+    for (from, entry) in removed.iter().enumerate() {
+        if entry.file_type != FileType::Regular {
+            continue;
+        }
+        let Some(targets) = candidates.get_mut(&entry.size) else { continue };
+        let Some(digest) = left_side.digest(entry, algorithm)? else { continue };
+        for position in 0..targets.len() {
+            if right_side.digest(&added[targets[position]], algorithm)?.as_ref() == Some(&digest) {
+                pairs.push((from, targets.remove(position)));
+                break;
+            }
+        }
+    }
+    Ok(pairs)
+}
+
+// ====================================================================
+// TESTES UNITÁRIOS
+// ====================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::scratch_dir;
+    use crate::{Archivus, ContentHasher, Timestamp};
+    use std::fs;
+    use std::time::{Duration, SystemTime};
+
+    fn entry(path: &str, file_type: FileType, content: &str) -> SnapshotEntry {
+        SnapshotEntry {
+            path: path.to_string(),
+            file_type,
+            size: content.len() as u64,
+            modified: Some(Timestamp::new(1_000, 0)),
+            mode: None,
+            link_target: None,
+            digest: (file_type == FileType::Regular).then(|| ContentHasher::digest(HashAlgorithm::Crc32, content.as_bytes())),
+        }
+    }
+
+    fn snapshot(root: &str, entries: Vec<SnapshotEntry>) -> Snapshot {
+        Snapshot {
+            root: PathBuf::from(root),
+            created: Timestamp::new(0, 0),
+            algorithm: Some(HashAlgorithm::Crc32),
+            entries,
+        }
+    }
+
+    #[test]
+    fn test_diff_snapshots() {
+        let left = snapshot("antes/", vec![
+            entry("a.txt", FileType::Regular, "abc"),
+            entry("b.txt", FileType::Regular, "mesmo tamanho"),
+            entry("velho", FileType::Directory, ""),
+            entry("velho/c.txt", FileType::Regular, "conteúdo movido"),
+            entry("velho/d.txt", FileType::Regular, "removido"),
+            entry("x", FileType::Regular, ""),
+        ]);
+        let right = snapshot("depois", vec![
+            entry("a.txt", FileType::Regular, "abc"),
+            entry("b.txt", FileType::Regular, "mesmo_tamanho"),
+            entry("novo", FileType::Directory, ""),
+            entry("novo/c.txt", FileType::Regular, "conteúdo movido"),
+            entry("novo/e.txt", FileType::Regular, "adicionado"),
+            entry("x", FileType::Directory, ""),
+        ]);
+
+        // Por metadados, b.txt não mudou; pelo conteúdo, sim. O conteúdo dos
+        // diretórios presentes em apenas um lado não aparece no texto
+        let report = diff(left.clone(), false, right.clone(), false, &DiffOptions::default()).unwrap();
+        assert_eq!(report.unchanged, 2);
+        assert_eq!(
+            report.to_string(),
+            "Only in depois: novo\n\
+             Only in antes/: velho\n\
+             File antes/velho/c.txt renamed to depois/novo/c.txt\n\
+             File antes/x is a regular file while file depois/x is a directory\n"
+        );
+
+        let options = DiffOptions { compare: CompareMode::Content, detect_renames: false, ..Default::default() };
+        let report = diff(left.clone(), false, right.clone(), false, &options).unwrap();
+        assert!(matches!(&report.changes[0], Change::Modified { left, .. } if left.path == "b.txt"));
+        assert_eq!(report.changes.iter().filter(|change| matches!(change, Change::Renamed { .. })).count(), 0);
+        assert_eq!(report.changes.len(), 8);
+
+        // Sem hashes no snapshot, a comparação por conteúdo não é possível
+        let mut plain = left.clone();
+        plain.algorithm = None;
+        plain.entries.iter_mut().for_each(|entry| entry.digest = None);
+        assert!(matches!(diff(plain, false, right, false, &options), Err(ArchivusError::InvalidPath(_))));
+        assert!(diff(left.clone(), false, left, false, &options).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_diff() {
+        let utils = Archivus::new();
+        let root = scratch_dir("diff");
+        let (left, right) = (root.join("esquerda"), root.join("direita"));
+        for dir in [&left, &right] {
+            fs::create_dir_all(dir.join("sub")).unwrap();
+            fs::write(dir.join("igual.txt"), "igual").unwrap();
+        }
+        fs::write(left.join("sub/velho.txt"), "renomeado").unwrap();
+        fs::write(right.join("sub/novo.txt"), "renomeado").unwrap();
+        fs::write(left.join("editado.txt"), "antes").unwrap();
+        fs::write(right.join("editado.txt"), "depois").unwrap();
+        fs::write(left.join("mesma_data.txt"), "aaaa").unwrap();
+        fs::write(right.join("mesma_data.txt"), "bbbb").unwrap();
+        fs::write(left.join("tipo"), "").unwrap();
+        fs::create_dir(right.join("tipo")).unwrap();
+
+        // Mesmo tamanho e mesma data: igual por metadados, diferente pelo
+        // conteúdo. igual.txt também recebe a mesma data nos dois lados
+        let time = SystemTime::UNIX_EPOCH + Duration::from_secs(1_600_000_000);
+        for dir in [&left, &right] {
+            for name in ["igual.txt", "mesma_data.txt"] {
+                fs::File::options().write(true).open(dir.join(name)).unwrap().set_modified(time).unwrap();
+            }
+        }
+
+        let report = utils.diff(&left, &right, &DiffOptions::default()).unwrap();
+        let text = report.to_string();
+        assert!(text.contains(&format!("Files {}/editado.txt and {}/editado.txt differ\n", left.display(), right.display())));
+        assert!(text.contains(&format!("File {}/sub/velho.txt renamed to {}/sub/novo.txt\n", left.display(), right.display())));
+        assert!(text.contains(&format!("File {}/tipo is a regular file while file {}/tipo is a directory\n", left.display(), right.display())));
+        assert!(!text.contains("mesma_data"));
+        assert_eq!((report.changes.len(), report.unchanged), (3, 3));
+
+        let content = DiffOptions { compare: CompareMode::Content, detect_renames: false, ..Default::default() };
+        let report = utils.diff(&left, &right, &content).unwrap();
+        let paths: Vec<&str> = report.changes.iter().map(Change::path).collect();
+        assert_eq!(paths, ["editado.txt", "mesma_data.txt", "sub/novo.txt", "sub/velho.txt", "tipo"]);
+        assert!(matches!(report.changes[2], Change::Added(_)));
+
+        // Snapshot salvo antes de uma mudança, comparado com o diretório
+        let before = utils.snapshot(&left, &DiffOptions::default().filter, Some(HashAlgorithm::Sha256)).unwrap();
+        fs::write(left.join("igual.txt"), "IGUAL").unwrap();
+        let report = utils.diff(before.clone(), left.as_path(), &content).unwrap();
+        assert_eq!(report.changes.len(), 1);
+        assert!(matches!(&report.changes[0], Change::Modified { right, .. } if right.path == "igual.txt"));
+        assert!(utils.diff(before.clone(), before, &content).unwrap().is_empty());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,351 @@
+// ====================================================================
+// ARQUIVOS DUPLICADOS
+// ====================================================================
+// Agrupa arquivos idênticos em etapas cada vez mais caras: tamanho,
+// hash do início, hash completo e, por fim, comparação byte a byte
+
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::hash::{DefaultHasher, Hasher};
+use std::io::{self, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+use crate::walk::Failure;
+use crate::{format_bytes, FileInfo, PartialResult};
+
+// Bytes do início do arquivo usados no hash parcial
+const PARTIAL_LEN: u64 = 4096;
+const BUFFER_LEN: usize = 64 * 1024;
+
+/// Grupo de arquivos com conteúdo idêntico
+#[derive(Debug, Clone)]
+pub struct DuplicateGroup {
+    /// Tamanho de cada arquivo, em bytes
+    pub size: u64,
+    /// Arquivos idênticos, em ordem de caminho
+    pub files: Vec<FileInfo>,
+}
+
+impl DuplicateGroup {
+    /// Espaço ocupado pelas cópias além da primeira
+    pub fn wasted_space(&self) -> u64 {
+        self.size * (self.files.len() as u64 - 1)
+    }
+
+    /// Formata o espaço desperdiçado de forma legível
+    pub fn formatted_wasted_space(&self) -> String {
+        format_bytes(self.wasted_space())
+    }
+}
+
+/// Resultado de `Archivus::find_duplicates`
+#[derive(Debug, Clone, Default)]
+pub struct DuplicateReport {
+    /// Grupos de arquivos idênticos, do maior para o menor desperdício
+    pub groups: Vec<DuplicateGroup>,
+    /// Arquivos examinados
+    pub files_scanned: usize,
+}
+
+impl DuplicateReport {
+    /// Espaço total ocupado pelas cópias redundantes
+    pub fn wasted_space(&self) -> u64 {
+        self.groups.iter().map(DuplicateGroup::wasted_space).sum()
+    }
+
+    /// Formata o espaço total desperdiçado de forma legível
+    pub fn formatted_wasted_space(&self) -> String {
+        format_bytes(self.wasted_space())
+    }
+
+    /// Número de arquivos redundantes (cópias além da primeira de cada grupo)
+    pub fn duplicate_count(&self) -> usize {
+        self.groups.iter().map(|group| group.files.len() - 1).sum()
+    }
+}
+
+// Identifica o mesmo arquivo alcançado por caminhos diferentes (links
+// físicos ou raízes sobrepostas)
+#[derive(PartialEq, Eq, Hash)]
+enum Identity {
+    Inode(u64, u64),
+    Path(PathBuf),
+}
+
+impl Identity {
+    fn of(file: &FileInfo) -> Self {
+        match (&file.unix, file.device) {
+            (Some(unix), Some(device)) => Identity::Inode(device, unix.inode),
+            _ => Identity::Path(file.path.clone()),
+        }
+    }
+}
+
+/// Agrupa arquivos idênticos
+///
+/// Arquivos vazios são ignorados e cada arquivo é considerado uma única vez,
+/// mesmo que apareça por vários caminhos (links físicos ou raízes
+/// sobrepostas). Um arquivo que não pode ser lido (ou que sumiu depois de
+/// listado) sai do seu grupo e é registrado em `failures`.
+pub(crate) fn find_duplicates(files: impl IntoIterator<Item = FileInfo>, mut failures: Vec<Failure>) -> PartialResult<DuplicateReport> {
+    let mut seen = HashSet::new();
+    let mut by_size: HashMap<u64, Vec<FileInfo>> = HashMap::new();
+    let mut files_scanned = 0;
+    for file in files {
+        if file.size == 0 || !seen.insert(Identity::of(&file)) {
+            continue;
+        }
+        files_scanned += 1;
+        by_size.entry(file.size).or_default().push(file);
+    }
+
+    let mut groups = Vec::new();
+    for (size, candidates) in by_size {
+        if candidates.len() < 2 {
+            continue;
+        }
+        for candidates in split_by_key(candidates, |path| hash_file(path, Some(PARTIAL_LEN)), &mut failures) {
+            // Arquivos pequenos já foram lidos por inteiro no hash parcial
+            let candidates = if size > PARTIAL_LEN {
+                split_by_key(candidates, |path| hash_file(path, None), &mut failures)
+            } else {
+                vec![candidates]
+            };
+            for candidates in candidates {
+                for mut files in split_identical(candidates, &mut failures) {
+                    files.sort_by(|a, b| a.path.cmp(&b.path));
+                    groups.push(DuplicateGroup { size, files });
+                }
+            }
+        }
+    }
+
+    groups.sort_by(|a, b| b.wasted_space().cmp(&a.wasted_space()).then_with(|| a.files[0].path.cmp(&b.files[0].path)));
+    PartialResult {
+        value: DuplicateReport { groups, files_scanned },
+        failures,
+    }
+}
+
+// Separa os arquivos por uma chave, mantendo apenas os grupos com mais de um
+fn split_by_key<F>(files: Vec<FileInfo>, key: F, failures: &mut Vec<Failure>) -> Vec<Vec<FileInfo>>
+where
+    F: Fn(&Path) -> io::Result<u64>,
+{
+    let mut groups: HashMap<u64, Vec<FileInfo>> = HashMap::new();
+    for file in files {
+        match key(&file.path) {
+            Ok(key) => groups.entry(key).or_default().push(file),
+            Err(e) => failures.push((file.path, e.into())),
+        }
+    }
+    groups.into_values().filter(|group| group.len() > 1).collect()
+}
+
+// Confirma byte a byte: cada arquivo é comparado com o primeiro de cada
+// subgrupo já formado
+fn split_identical(files: Vec<FileInfo>, failures: &mut Vec<Failure>) -> Vec<Vec<FileInfo>> {
+    let mut groups: Vec<Vec<FileInfo>> = Vec::new();
+    'files: for file in files {
+        let mut index = 0;
+        while index < groups.len() {
+            match same_content(&groups[index][0].path, &file.path) {
+                Ok(true) => {
+                    groups[index].push(file);
+                    continue 'files;
+                }
+                Ok(false) => index += 1,
+                Err((path, e)) => {
+                    if path == file.path {
+                        failures.push((path, e));
+                        continue 'files;
+                    }
+                    // O primeiro do subgrupo ficou ilegível: o próximo, de
+                    // conteúdo idêntico, passa a representá-lo
+                    failures.push((groups[index].remove(0).path, e));
+                    if groups[index].is_empty() {
+                        groups.remove(index);
+                    }
+                }
+            }
+        }
+        groups.push(vec![file]);
+    }
+    groups.into_iter().filter(|group| group.len() > 1).collect()
+}
+
+// Hash (não criptográfico) do conteúdo, ou dos primeiros `limit` bytes
+fn hash_file(path: &Path, limit: Option<u64>) -> io::Result<u64> {
+    let file = File::open(path)?;
+    let mut reader: Box<dyn Read> = match limit {
+        Some(limit) => Box::new(file.take(limit)),
+        None => Box::new(file),
+    };
+
+    let mut hasher = DefaultHasher::new();
+    let mut buffer = vec![0; BUFFER_LEN];
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            return Ok(hasher.finish());
+        }
+        hasher.write(&buffer[..read]);
+    }
+}
+
+// Compara dois arquivos; em caso de erro, informa qual deles falhou
+fn same_content(a: &Path, b: &Path) -> Result<bool, Failure> {
+    fn failure(path: &Path) -> impl FnOnce(io::Error) -> Failure + '_ {
+        move |e| (path.to_path_buf(), e.into())
+    }
+    let mut reader_a = BufReader::with_capacity(BUFFER_LEN, File::open(a).map_err(failure(a))?);
+    let mut reader_b = BufReader::with_capacity(BUFFER_LEN, File::open(b).map_err(failure(b))?);
+    let (mut chunk_a, mut chunk_b) = (vec![0; BUFFER_LEN], vec![0; BUFFER_LEN]);
+    loop {
+        let read = read_full(&mut reader_a, &mut chunk_a).map_err(failure(a))?;
+        if read != read_full(&mut reader_b, &mut chunk_b).map_err(failure(b))? || chunk_a[..read] != chunk_b[..read] {
+            return Ok(false);
+        }
+        if read == 0 {
+            return Ok(true);
+        }
+    }
+}
+
+// Lê até encher o buffer ou chegar ao fim do arquivo
+fn read_full(reader: &mut impl Read, buffer: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buffer.len() {
+        let read = reader.read(&mut buffer[filled..])?;
+        if read == 0 {
+            break;
+        }
+        filled += read;
+    }
+    Ok(filled)
+}
+
+// ====================================================================
+// TESTES UNITÁRIOS
+// ====================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::scratch_dir;
+    use crate::{Archivus, ArchivusError, FileFilter};
+    use std::fs;
+
+    #[test]
+    fn test_find_duplicates_stages() {
+        let root = scratch_dir("duplicates_stages");
+        fs::create_dir_all(&root).unwrap();
+
+        // Mesmo tamanho e mesmo início, diferentes apenas no fim
+        let mut big = vec![7u8; 10_000];
+        fs::write(root.join("grande1"), &big).unwrap();
+        fs::write(root.join("grande2"), &big).unwrap();
+        big[9_999] = 8;
+        fs::write(root.join("grande3"), &big).unwrap();
+        fs::write(root.join("pequeno1"), "abc").unwrap();
+        fs::write(root.join("pequeno2"), "abd").unwrap();
+        fs::write(root.join("vazio1"), "").unwrap();
+        fs::write(root.join("vazio2"), "").unwrap();
+
+        let files = ["grande1", "grande2", "grande3", "pequeno1", "pequeno2", "vazio1", "vazio2"]
+            .map(|name| FileInfo::new(root.join(name)).unwrap());
+        let result = find_duplicates(files, Vec::new());
+        assert!(result.is_complete());
+        let report = result.value;
+        assert_eq!(report.files_scanned, 5);
+        assert_eq!(report.groups.len(), 1);
+        let names: Vec<&str> = report.groups[0].files.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["grande1", "grande2"]);
+        assert_eq!(report.wasted_space(), 10_000);
+        assert_eq!(report.formatted_wasted_space(), "9.77 KB");
+
+        assert!(!same_content(&root.join("grande1"), &root.join("grande3")).unwrap());
+        assert_eq!(hash_file(&root.join("grande1"), Some(PARTIAL_LEN)).unwrap(), hash_file(&root.join("grande3"), Some(PARTIAL_LEN)).unwrap());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_find_duplicates_vanished_file() {
+        let root = scratch_dir("duplicates_vanished");
+        fs::create_dir_all(&root).unwrap();
+
+        for name in ["a", "b", "c", "d"] {
+            fs::write(root.join(name), "mesmo conteúdo").unwrap();
+        }
+        let files: Vec<FileInfo> = ["a", "b", "c", "d"].iter().map(|name| FileInfo::new(root.join(name)).unwrap()).collect();
+
+        // Removidos entre a listagem e a leitura
+        fs::remove_file(root.join("b")).unwrap();
+        fs::remove_file(root.join("d")).unwrap();
+
+        let result = find_duplicates(files, Vec::new());
+        assert_eq!(result.value.groups.len(), 1);
+        let names: Vec<&str> = result.value.groups[0].files.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["a", "c"]);
+        let mut failed: Vec<PathBuf> = result.failures.iter().map(|(path, _)| path.clone()).collect();
+        failed.sort();
+        assert_eq!(failed, [root.join("b"), root.join("d")]);
+        assert!(result.failures.iter().all(|(_, e)| matches!(e, ArchivusError::NotFound(_))));
+
+        // O primeiro do subgrupo some durante a comparação byte a byte
+        let files = vec![FileInfo::new(root.join("a")).unwrap(), FileInfo::new(root.join("c")).unwrap()];
+        fs::remove_file(root.join("a")).unwrap();
+        let mut failures = Vec::new();
+        assert!(split_identical(files, &mut failures).is_empty());
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].0, root.join("a"));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    // Links físicos só são reconhecidos pelo inode em sistemas Unix
+    #[cfg(unix)]
+    #[test]
+    fn test_find_duplicates() {
+        let utils = Archivus::new();
+        let root = scratch_dir("find_duplicates");
+        fs::create_dir_all(root.join("backup1")).unwrap();
+        fs::create_dir_all(root.join("backup2/fotos")).unwrap();
+        fs::write(root.join("backup1/a.txt"), "conteúdo repetido").unwrap();
+        fs::write(root.join("backup2/a.txt"), "conteúdo repetido").unwrap();
+        fs::write(root.join("backup2/fotos/copia.txt"), "conteúdo repetido").unwrap();
+        fs::write(root.join("backup1/foto.jpg"), vec![1u8; 5000]).unwrap();
+        fs::write(root.join("backup2/fotos/foto.jpg"), vec![1u8; 5000]).unwrap();
+        fs::write(root.join("backup1/unico.txt"), "único").unwrap();
+        fs::hard_link(root.join("backup1/unico.txt"), root.join("backup2/link.txt")).unwrap();
+
+        let filter = FileFilter {
+            recursive: true,
+            ..Default::default()
+        };
+        // Raízes sobrepostas não duplicam os arquivos
+        let result = utils.find_duplicates(&[root.join("backup1"), root.join("backup2"), root.clone()], &filter).unwrap();
+        assert!(result.is_complete());
+        let report = result.value;
+        assert_eq!(report.groups.len(), 2);
+        assert_eq!(report.groups[0].size, 5000);
+        assert_eq!(report.groups[0].files.len(), 2);
+        assert_eq!(report.groups[1].files.len(), 3);
+        assert_eq!(report.duplicate_count(), 3);
+        assert_eq!(report.wasted_space(), 5000 + 2 * "conteúdo repetido".len() as u64);
+        assert_eq!(report.groups[0].formatted_wasted_space(), format_bytes(5000));
+
+        // O filtro restringe os candidatos
+        let only_txt = FileFilter {
+            recursive: true,
+            extensions: Some(vec!["txt".to_string()]),
+            ..Default::default()
+        };
+        let report = utils.find_duplicates(&[&root], &only_txt).unwrap().value;
+        assert_eq!(report.groups.len(), 1);
+        assert_eq!(report.files_scanned, 4);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,454 @@
+// ====================================================================
+// PADRÕES GLOB
+// ====================================================================
+// Correspondência de padrões com `*`, `?`, classes `[...]`, `**` e
+// alternativas `{a,b}`, aplicados segmento a segmento sobre caminhos relativos
+
+use std::path::{Component, Path};
+
+use crate::ArchivusError;
+
+// Elemento de um segmento do padrão
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// Caractere literal
+    Literal(char),
+    /// `?`: exatamente um caractere
+    AnyChar,
+    /// `*`: qualquer sequência de caracteres dentro do segmento
+    Star,
+    /// `[...]`: um caractere dentro (ou fora, se negada) dos intervalos
+    Class { negated: bool, ranges: Vec<(char, char)> },
+}
+
+impl Token {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            Token::Literal(l) => *l == c,
+            Token::AnyChar => true,
+            Token::Star => false,
+            Token::Class { negated, ranges } => {
+                ranges.iter().any(|&(lo, hi)| lo <= c && c <= hi) != *negated
+            }
+        }
+    }
+}
+
+// Segmento do padrão, entre barras
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    /// `**`: zero ou mais segmentos do caminho
+    AnyPath,
+    /// Segmento comum, que corresponde a exatamente um segmento do caminho
+    Part(Vec<Token>),
+}
+
+// Limite de alternativas geradas pela expansão de chaves
+const MAX_ALTERNATIVES: usize = 4096;
+
+/// Padrão glob compilado, comparado com caminhos relativos
+///
+/// Sintaxe suportada:
+/// - `*`: qualquer sequência de caracteres dentro de um segmento do caminho
+/// - `?`: exatamente um caractere
+/// - `**`: zero ou mais segmentos inteiros (`src/**/*.rs`); no final do
+///   padrão (`target/**`) corresponde a tudo dentro do diretório
+/// - `[abc]`, `[a-z]`, `[!x]` ou `[^x]`: classes de caracteres
+/// - `{a,b}`: alternativas, que podem conter `/` e ser aninhadas
+/// - `\`: escapa o caractere seguinte (`\*`, `\{`...)
+///
+/// A comparação diferencia maiúsculas de minúsculas e `*` também corresponde
+/// a nomes iniciados por ponto.
+///
+/// # Exemplos
+/// ```rust
+/// use archivus::Glob;
+///
+/// let glob = Glob::new("src/**/*.{rs,toml}")?;
+///
+/// assert!(glob.matches("src/lib.rs"));
+/// assert!(glob.matches("src/bin/cli/Cargo.toml"));
+/// assert!(!glob.matches("tests/lib.rs"));
+/// # Ok::<(), archivus::ArchivusError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Glob {
+    pattern: String,
+    // Uma lista de segmentos para cada alternativa gerada pelas chaves
+    alternatives: Vec<Vec<Segment>>,
+}
+
+impl Glob {
+    /// Compila um padrão glob
+    ///
+    /// Retorna `ArchivusError::InvalidPattern` para chaves ou colchetes sem
+    /// fechamento, `\` no final do padrão ou expansões de chaves grandes demais.
+    pub fn new(pattern: &str) -> Result<Self, ArchivusError> {
+        let alternatives = expand_braces(pattern)?
+            .iter()
+            .map(|alternative| parse_segments(alternative, true))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            pattern: pattern.to_string(),
+            alternatives,
+        })
+    }
+
+    /// Compila um padrão no formato do `.gitignore`: sem chaves e com
+    /// classes sem `]` de fechamento tratadas como `[` literal
+    pub(crate) fn gitignore(pattern: &str) -> Self {
+        let segments = parse_segments(pattern, false).unwrap_or_default();
+
+        Self {
+            pattern: pattern.to_string(),
+            alternatives: vec![segments],
+        }
+    }
+
+    /// Retorna o padrão original
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Verifica se um caminho relativo corresponde ao padrão
+    ///
+    /// Componentes `.` são ignorados, então `./src/lib.rs` equivale a `src/lib.rs`.
+    /// Um caminho com algum componente que não é UTF-8 válido nunca corresponde.
+    pub fn matches<P: AsRef<Path>>(&self, path: P) -> bool {
+        path_segments(path.as_ref()).is_some_and(|segments| self.matches_segments(&segments))
+    }
+
+    /// Verifica se os segmentos de um caminho correspondem ao padrão
+    pub(crate) fn matches_segments(&self, path: &[&str]) -> bool {
+        self.alternatives.iter().any(|segments| {
+            simulate(segments, path).contains(&segments.len())
+        })
+    }
+
+    /// Verifica se algum caminho dentro do diretório indicado pelos segmentos
+    /// pode corresponder ao padrão
+    pub(crate) fn could_match_within(&self, dir: &[&str]) -> bool {
+        self.alternatives.iter().any(|segments| {
+            simulate(segments, dir).iter().any(|&state| state < segments.len())
+        })
+    }
+}
+
+impl std::fmt::Display for Glob {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.pattern)
+    }
+}
+
+// Segmentos normais de um caminho, sem `.` nem raiz. Retorna `None` se
+// algum segmento não for UTF-8 válido: descartá-lo deslocaria os demais
+pub(crate) fn path_segments(path: &Path) -> Option<Vec<&str>> {
+    path.components()
+        .filter_map(|component| match component {
+            Component::Normal(segment) => Some(segment.to_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+// Simula o padrão como um autômato sobre os segmentos do caminho e retorna
+// as posições do padrão alcançadas depois de consumir todos eles
+fn simulate(segments: &[Segment], path: &[&str]) -> Vec<usize> {
+    let path: Vec<Vec<char>> = path.iter().map(|s| s.chars().collect()).collect();
+
+    // `**` pode não consumir nada: inclui as posições seguintes
+    let close = |states: &mut Vec<usize>| {
+        let mut i = 0;
+        while i < states.len() {
+            let state = states[i];
+            if segments.get(state) == Some(&Segment::AnyPath) && !states.contains(&(state + 1)) {
+                states.push(state + 1);
+            }
+            i += 1;
+        }
+    };
+
+    let mut states = vec![0];
+    close(&mut states);
+
+    for text in &path {
+        let mut next = Vec::new();
+        for &state in &states {
+            let reached = match segments.get(state) {
+                Some(Segment::AnyPath) => Some(state),
+                Some(Segment::Part(tokens)) if match_tokens(tokens, text) => Some(state + 1),
+                _ => None,
+            };
+            if let Some(reached) = reached
+                && !next.contains(&reached)
+            {
+                next.push(reached);
+            }
+        }
+        close(&mut next);
+        if next.is_empty() {
+            return next;
+        }
+        states = next;
+    }
+
+    states
+}
+
+// Expande `{a,b}` em todas as alternativas, preservando escapes
+fn expand_braces(pattern: &str) -> Result<Vec<String>, ArchivusError> {
+    let chars: Vec<char> = pattern.chars().collect();
+
+    // Procura a primeira chave aberta que não está escapada
+    let mut i = 0;
+    let open = loop {
+        match chars.get(i) {
+            None => return Ok(vec![pattern.to_string()]),
+            Some('\\') => i += 2,
+            Some('{') => break i,
+            Some(_) => i += 1,
+        }
+    };
+
+    // Encontra a chave correspondente e as vírgulas do nível mais externo
+    let mut depth = 0;
+    let mut commas = Vec::new();
+    let mut close = None;
+    let mut i = open + 1;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 1,
+            '{' => depth += 1,
+            '}' if depth == 0 => {
+                close = Some(i);
+                break;
+            }
+            '}' => depth -= 1,
+            ',' if depth == 0 => commas.push(i),
+            _ => {}
+        }
+        i += 1;
+    }
+    let close = close.ok_or_else(|| {
+        ArchivusError::InvalidPattern(format!("'{{' sem '}}' correspondente em '{}'", pattern))
+    })?;
+
+    let prefix: String = chars[..open].iter().collect();
+    let suffix: String = chars[close + 1..].iter().collect();
+    let bounds: Vec<usize> = std::iter::once(open).chain(commas).chain(std::iter::once(close)).collect();
+
+    let mut expanded = Vec::new();
+    for pair in bounds.windows(2) {
+        let alternative: String = chars[pair[0] + 1..pair[1]].iter().collect();
+        for rest in expand_braces(&format!("{}{}{}", prefix, alternative, suffix))? {
+            expanded.push(rest);
+            if expanded.len() > MAX_ALTERNATIVES {
+                return Err(ArchivusError::InvalidPattern(format!(
+                    "'{}' gera mais de {} alternativas", pattern, MAX_ALTERNATIVES
+                )));
+            }
+        }
+    }
+
+    Ok(expanded)
+}
+
+// Divide o padrão em segmentos entre barras
+fn parse_segments(pattern: &str, strict: bool) -> Result<Vec<Segment>, ArchivusError> {
+    let mut segments: Vec<Segment> = Vec::new();
+
+    for part in pattern.split('/').filter(|part| !part.is_empty() && *part != ".") {
+        let segment = if part == "**" {
+            Segment::AnyPath
+        } else {
+            Segment::Part(parse_tokens(part, strict).map_err(|msg| {
+                ArchivusError::InvalidPattern(format!("{} em '{}'", msg, pattern))
+            })?)
+        };
+
+        // `**/**` equivale a um único `**`
+        if segment == Segment::AnyPath && segments.last() == Some(&Segment::AnyPath) {
+            continue;
+        }
+        segments.push(segment);
+    }
+
+    // `a/**` corresponde a tudo dentro de `a`, mas não ao próprio `a`
+    if segments.len() > 1 && segments.last() == Some(&Segment::AnyPath) {
+        segments.insert(segments.len() - 1, Segment::Part(vec![Token::Star]));
+    }
+
+    Ok(segments)
+}
+
+// Converte um segmento do padrão em tokens. No modo estrito, classes sem
+// fechamento e `\` no final são erros; caso contrário viram literais
+fn parse_tokens(part: &str, strict: bool) -> Result<Vec<Token>, &'static str> {
+    let chars: Vec<char> = part.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            '*' => {
+                // `**` dentro de um segmento equivale a `*`
+                if tokens.last() != Some(&Token::Star) {
+                    tokens.push(Token::Star);
+                }
+            }
+            '?' => tokens.push(Token::AnyChar),
+            '\\' if i + 1 < chars.len() => {
+                i += 1;
+                tokens.push(Token::Literal(chars[i]));
+            }
+            '\\' if strict => return Err("'\\' no final do padrão"),
+            '[' => match parse_class(&chars[i + 1..]) {
+                Some((class, consumed)) => {
+                    tokens.push(class);
+                    i += consumed;
+                }
+                None if strict => return Err("'[' sem ']' correspondente"),
+                None => tokens.push(Token::Literal('[')),
+            },
+            c => tokens.push(Token::Literal(c)),
+        }
+        i += 1;
+    }
+
+    Ok(tokens)
+}
+
+// Lê uma classe a partir do caractere seguinte ao `[`. Retorna o token e
+// quantos caracteres foram consumidos, incluindo o `]`
+fn parse_class(chars: &[char]) -> Option<(Token, usize)> {
+    let mut i = 0;
+    let negated = matches!(chars.first(), Some('!') | Some('^'));
+    if negated {
+        i += 1;
+    }
+
+    let mut ranges = Vec::new();
+    let mut first = true;
+
+    loop {
+        let c = *chars.get(i)?;
+        if c == ']' && !first {
+            return Some((Token::Class { negated, ranges }, i + 1));
+        }
+        first = false;
+
+        let lo = if c == '\\' {
+            i += 1;
+            *chars.get(i)?
+        } else {
+            c
+        };
+
+        if chars.get(i + 1) == Some(&'-') && chars.get(i + 2).is_some_and(|&hi| hi != ']') {
+            ranges.push((lo, chars[i + 2]));
+            i += 3;
+        } else {
+            ranges.push((lo, lo));
+            i += 1;
+        }
+    }
+}
+
+fn match_tokens(tokens: &[Token], text: &[char]) -> bool {
+    backtrack(tokens, text, |token| *token == Token::Star, |token, c| token.matches(*c))
+}
+
+// Correspondência com curingas sem recursão: ao falhar, volta apenas para o
+// último curinga visto, fazendo-o consumir mais um elemento
+fn backtrack<P, T, W, M>(pattern: &[P], text: &[T], is_wildcard: W, matches: M) -> bool
+where
+    W: Fn(&P) -> bool,
+    M: Fn(&P, &T) -> bool,
+{
+    let (mut p, mut t) = (0, 0);
+    let mut resume: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && is_wildcard(&pattern[p]) {
+            resume = Some((p, t));
+            p += 1;
+        } else if p < pattern.len() && matches(&pattern[p], &text[t]) {
+            p += 1;
+            t += 1;
+        } else if let Some((wildcard, start)) = resume {
+            p = wildcard + 1;
+            t = start + 1;
+            resume = Some((wildcard, start + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(is_wildcard)
+}
+
+// ====================================================================
+// TESTES UNITÁRIOS
+// ====================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, path: &str) -> bool {
+        Glob::new(pattern).unwrap().matches(path)
+    }
+
+    #[test]
+    fn test_glob_segments() {
+        assert!(matches("*.rs", "main.rs"));
+        assert!(!matches("*.rs", "src/main.rs"));
+        assert!(matches("src/*.rs", "src/main.rs"));
+        assert!(matches("**/*.rs", "main.rs"));
+        assert!(matches("**/*.rs", "a/b/c/main.rs"));
+        assert!(matches("a/**/b", "a/b"));
+        assert!(matches("a/**/b", "a/x/y/b"));
+        assert!(!matches("a/**", "a"));
+        assert!(matches("a/**", "a/x/y"));
+        assert!(matches("file?.[a-c]", "file1.b"));
+        assert!(!matches("file?.[!a-c]", "file1.b"));
+        assert!(matches("[[]x\\*", "[x*"));
+        assert!(Glob::gitignore("[x").matches("[x"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_glob_non_utf8() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = Path::new(OsStr::from_bytes(b"\xff")).join("b/c.txt");
+        assert!(!Glob::new("b/c.txt").unwrap().matches(&path));
+        assert!(!Glob::new("**/*.txt").unwrap().matches(&path));
+        assert_eq!(path_segments(&path), None);
+    }
+
+    #[test]
+    fn test_glob_braces_and_errors() {
+        assert!(matches("src/**/*.{rs,toml}", "src/a/b.toml"));
+        assert!(matches("{src,tests}/*.rs", "tests/it.rs"));
+        assert!(matches("a{b,c{d,e}}f", "acef"));
+        assert!(matches("\\{a,b\\}", "{a,b}"));
+        assert!(!matches("*.{rs,toml}", "x.md"));
+
+        assert!(Glob::new("*.{rs").is_err());
+        assert!(Glob::new("[a-z").is_err());
+        assert!(Glob::new("abc\\").is_err());
+    }
+
+    #[test]
+    fn test_glob_could_match_within() {
+        let glob = Glob::new("src/{bin,lib}/*.rs").unwrap();
+        assert!(glob.could_match_within(&["src"]));
+        assert!(glob.could_match_within(&["src", "bin"]));
+        assert!(!glob.could_match_within(&["tests"]));
+        assert!(!glob.could_match_within(&["src", "bin", "x"]));
+        assert!(Glob::new("**/*.rs").unwrap().could_match_within(&["a", "b", "c"]));
+    }
+}
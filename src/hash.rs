@@ -0,0 +1,714 @@
+// ====================================================================
+// HASHES DE CONTEÚDO
+// ====================================================================
+// Implementações incrementais, sem dependências, de SHA-256, BLAKE2b-512,
+// CRC32 e xxHash64, para arquivos e árvores de diretórios
+
+use std::fmt;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::{ArchivusError, FileInfo};
+
+const BUFFER_LEN: usize = 64 * 1024;
+
+/// Algoritmo de hash
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HashAlgorithm {
+    /// SHA-256 (32 bytes), compatível com `sha256sum`
+    Sha256,
+    /// BLAKE2b com saída de 64 bytes, compatível com `b2sum`
+    Blake2b,
+    /// CRC-32 IEEE (4 bytes), o mesmo de zip, gzip e PNG
+    Crc32,
+    /// xxHash64 com semente 0 (8 bytes), não criptográfico e muito rápido
+    XxHash64,
+}
+
+impl HashAlgorithm {
+    /// Todos os algoritmos disponíveis
+    pub const ALL: [HashAlgorithm; 4] = [HashAlgorithm::Sha256, HashAlgorithm::Blake2b, HashAlgorithm::Crc32, HashAlgorithm::XxHash64];
+
+    /// Nome do algoritmo (`sha256`, `blake2b`, `crc32`, `xxh64`)
+    pub fn name(&self) -> &'static str {
+        match self {
+            HashAlgorithm::Sha256 => "sha256",
+            HashAlgorithm::Blake2b => "blake2b",
+            HashAlgorithm::Crc32 => "crc32",
+            HashAlgorithm::XxHash64 => "xxh64",
+        }
+    }
+
+    /// Tamanho do resultado, em bytes
+    pub fn digest_len(&self) -> usize {
+        match self {
+            HashAlgorithm::Sha256 => 32,
+            HashAlgorithm::Blake2b => 64,
+            HashAlgorithm::Crc32 => 4,
+            HashAlgorithm::XxHash64 => 8,
+        }
+    }
+}
+
+impl fmt::Display for HashAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for HashAlgorithm {
+    type Err = ArchivusError;
+
+    /// Aceita o nome do algoritmo, sem distinção de maiúsculas, e variações
+    /// comuns (`sha-256`, `blake2b-512`, `b2`, `xxhash64`)
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.to_lowercase().as_str() {
+            "sha256" | "sha-256" => Ok(HashAlgorithm::Sha256),
+            "blake2b" | "blake2b-512" | "blake2" | "b2" => Ok(HashAlgorithm::Blake2b),
+            "crc32" | "crc-32" => Ok(HashAlgorithm::Crc32),
+            "xxh64" | "xxhash64" | "xxhash" => Ok(HashAlgorithm::XxHash64),
+            _ => Err(ArchivusError::ParseError {
+                position: 0,
+                message: format!("algoritmo de hash desconhecido '{}'", name),
+            }),
+        }
+    }
+}
+
+/// Resultado de um hash
+///
+/// `Display` escreve o valor em hexadecimal minúsculo.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Digest {
+    pub(crate) algorithm: HashAlgorithm,
+    pub(crate) bytes: Vec<u8>,
+}
+
+impl Digest {
+    /// Lê um resultado em hexadecimal (maiúsculas ou minúsculas)
+    ///
+    /// # Erros
+    /// Retorna `ArchivusError::ParseError` se o texto não for hexadecimal ou
+    /// não tiver o tamanho do algoritmo.
+    pub fn from_hex(algorithm: HashAlgorithm, hex: &str) -> Result<Self, ArchivusError> {
+        let error = |position: usize, message: &str| ArchivusError::ParseError { position, message: message.to_string() };
+        if hex.len() != algorithm.digest_len() * 2 {
+            return Err(error(0, &format!("{} tem {} dígitos hexadecimais, não {}", algorithm, algorithm.digest_len() * 2, hex.len())));
+        }
+        if let Some(position) = hex.find(|c: char| !c.is_ascii_hexdigit()) {
+            return Err(error(position, "dígito hexadecimal inválido"));
+        }
+
+        let bytes = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).expect("dígitos já validados"))
+            .collect();
+        Ok(Self { algorithm, bytes })
+    }
+
+    /// Algoritmo usado
+    pub fn algorithm(&self) -> HashAlgorithm {
+        self.algorithm
+    }
+
+    /// Valor em bytes
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Valor em hexadecimal minúsculo
+    pub fn to_hex(&self) -> String {
+        self.bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    /// Valor em base64 padrão (RFC 4648, com `=`)
+    pub fn to_base64(&self) -> String {
+        const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+        let mut encoded = String::with_capacity(self.bytes.len().div_ceil(3) * 4);
+        for chunk in self.bytes.chunks(3) {
+            let n = (chunk[0] as u32) << 16 | (*chunk.get(1).unwrap_or(&0) as u32) << 8 | *chunk.get(2).unwrap_or(&0) as u32;
+            for i in 0..4 {
+                if i <= chunk.len() {
+                    encoded.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+                } else {
+                    encoded.push('=');
+                }
+            }
+        }
+        encoded
+    }
+}
+
+impl fmt::Display for Digest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
+
+/// Hash de um arquivo, retornado por `Archivus::hash_files`
+#[derive(Debug, Clone)]
+pub struct FileHash {
+    /// Arquivo cujo conteúdo foi lido
+    pub file: FileInfo,
+    /// Hash do conteúdo do arquivo
+    pub digest: Digest,
+}
+
+/// Cálculo incremental de um hash
+///
+/// # Exemplos
+/// ```rust
+/// use archivus::{ContentHasher, HashAlgorithm};
+///
+/// let mut hasher = ContentHasher::new(HashAlgorithm::Sha256);
+/// hasher.update(b"ab");
+/// hasher.update(b"c");
+/// assert_eq!(
+///     hasher.finalize().to_hex(),
+///     "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct ContentHasher {
+    state: State,
+}
+
+#[derive(Debug, Clone)]
+enum State {
+    Sha256(Sha256),
+    Blake2b(Box<Blake2b>),
+    Crc32(u32),
+    XxHash64(Box<XxHash64>),
+}
+
+impl ContentHasher {
+    /// Cria um cálculo vazio
+    pub fn new(algorithm: HashAlgorithm) -> Self {
+        let state = match algorithm {
+            HashAlgorithm::Sha256 => State::Sha256(Sha256::new()),
+            HashAlgorithm::Blake2b => State::Blake2b(Box::new(Blake2b::new())),
+            HashAlgorithm::Crc32 => State::Crc32(!0),
+            HashAlgorithm::XxHash64 => State::XxHash64(Box::new(XxHash64::new())),
+        };
+        Self { state }
+    }
+
+    /// Acrescenta dados
+    pub fn update(&mut self, data: &[u8]) {
+        match &mut self.state {
+            State::Sha256(sha) => sha.update(data),
+            State::Blake2b(blake) => blake.update(data),
+            State::Crc32(crc) => *crc = crc32_update(*crc, data),
+            State::XxHash64(xxh) => xxh.update(data),
+        }
+    }
+
+    /// Termina o cálculo e retorna o resultado
+    pub fn finalize(self) -> Digest {
+        let (algorithm, bytes) = match self.state {
+            State::Sha256(sha) => (HashAlgorithm::Sha256, sha.finalize()),
+            State::Blake2b(blake) => (HashAlgorithm::Blake2b, blake.finalize()),
+            State::Crc32(crc) => (HashAlgorithm::Crc32, (!crc).to_be_bytes().to_vec()),
+            State::XxHash64(xxh) => (HashAlgorithm::XxHash64, xxh.finalize().to_be_bytes().to_vec()),
+        };
+        Digest { algorithm, bytes }
+    }
+
+    /// Hash de um trecho de memória
+    pub fn digest(algorithm: HashAlgorithm, data: &[u8]) -> Digest {
+        let mut hasher = Self::new(algorithm);
+        hasher.update(data);
+        hasher.finalize()
+    }
+}
+
+/// Hash do conteúdo de um arquivo, lido em blocos
+pub(crate) fn hash_file(path: &Path, algorithm: HashAlgorithm) -> Result<Digest, ArchivusError> {
+    let mut file = File::open(path)?;
+    let mut hasher = ContentHasher::new(algorithm);
+    let mut buffer = vec![0; BUFFER_LEN];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            return Ok(hasher.finalize());
+        }
+        hasher.update(&buffer[..read]);
+    }
+}
+
+// ====================================================================
+// SHA-256 (FIPS 180-4)
+// ====================================================================
+
+const SHA256_K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+const SHA256_IV: [u32; 8] = [0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19];
+
+#[derive(Debug, Clone)]
+struct Sha256 {
+    state: [u32; 8],
+    buffer: [u8; 64],
+    buffer_len: usize,
+    length: u64,
+}
+
+impl Sha256 {
+    fn new() -> Self {
+        Self {
+            state: SHA256_IV,
+            buffer: [0; 64],
+            buffer_len: 0,
+            length: 0,
+        }
+    }
+
+    fn update(&mut self, mut data: &[u8]) {
+        self.length += data.len() as u64;
+        while !data.is_empty() {
+            let take = (64 - self.buffer_len).min(data.len());
+            self.buffer[self.buffer_len..self.buffer_len + take].copy_from_slice(&data[..take]);
+            self.buffer_len += take;
+            data = &data[take..];
+            if self.buffer_len == 64 {
+                let block = self.buffer;
+                self.compress(&block);
+                self.buffer_len = 0;
+            }
+        }
+    }
+
+    fn finalize(mut self) -> Vec<u8> {
+        let bit_length = self.length.wrapping_mul(8);
+        let padding_len = if self.buffer_len < 56 { 56 - self.buffer_len } else { 120 - self.buffer_len };
+        let mut padding = vec![0u8; padding_len];
+        padding[0] = 0x80;
+        self.update(&padding);
+        self.update(&bit_length.to_be_bytes());
+        self.state.iter().flat_map(|word| word.to_be_bytes()).collect()
+    }
+
+    fn compress(&mut self, block: &[u8; 64]) {
+        let mut w = [0u32; 64];
+        for (i, word) in block.chunks_exact(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = self.state;
+        for i in 0..64 {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ (!e & g);
+            let t1 = h.wrapping_add(s1).wrapping_add(ch).wrapping_add(SHA256_K[i]).wrapping_add(w[i]);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(maj);
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+
+        for (state, value) in self.state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *state = state.wrapping_add(value);
+        }
+    }
+}
+
+// ====================================================================
+// BLAKE2b (RFC 7693)
+// ====================================================================
+
+const BLAKE2B_IV: [u64; 8] = [
+    0x6a09e667f3bcc908, 0xbb67ae8584caa73b, 0x3c6ef372fe94f82b, 0xa54ff53a5f1d36f1,
+    0x510e527fade682d1, 0x9b05688c2b3e6c1f, 0x1f83d9abfb41bd6b, 0x5be0cd19137e2179,
+];
+
+const BLAKE2B_SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+const BLAKE2B_OUT_LEN: usize = 64;
+
+#[derive(Debug, Clone)]
+struct Blake2b {
+    state: [u64; 8],
+    buffer: [u8; 128],
+    buffer_len: usize,
+    counter: u128,
+}
+
+impl Blake2b {
+    fn new() -> Self {
+        let mut state = BLAKE2B_IV;
+        // Parâmetros: sem chave, saída de 64 bytes, fanout e profundidade 1
+        state[0] ^= 0x0101_0000 ^ BLAKE2B_OUT_LEN as u64;
+        Self {
+            state,
+            buffer: [0; 128],
+            buffer_len: 0,
+            counter: 0,
+        }
+    }
+
+    // O último bloco só é comprimido em `finalize`, com a marca de fim
+    fn update(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            if self.buffer_len == 128 {
+                self.counter += 128;
+                let block = self.buffer;
+                self.compress(&block, false);
+                self.buffer_len = 0;
+            }
+            let take = (128 - self.buffer_len).min(data.len());
+            self.buffer[self.buffer_len..self.buffer_len + take].copy_from_slice(&data[..take]);
+            self.buffer_len += take;
+            data = &data[take..];
+        }
+    }
+
+    fn finalize(mut self) -> Vec<u8> {
+        self.counter += self.buffer_len as u128;
+        self.buffer[self.buffer_len..].fill(0);
+        let block = self.buffer;
+        self.compress(&block, true);
+        self.state.iter().flat_map(|word| word.to_le_bytes()).take(BLAKE2B_OUT_LEN).collect()
+    }
+
+    fn compress(&mut self, block: &[u8; 128], last: bool) {
+        let mut m = [0u64; 16];
+        for (i, word) in block.chunks_exact(8).enumerate() {
+            m[i] = u64::from_le_bytes(word.try_into().expect("blocos de 8 bytes"));
+        }
+
+        let mut v = [0u64; 16];
+        v[..8].copy_from_slice(&self.state);
+        v[8..].copy_from_slice(&BLAKE2B_IV);
+        v[12] ^= self.counter as u64;
+        v[13] ^= (self.counter >> 64) as u64;
+        if last {
+            v[14] = !v[14];
+        }
+
+        for round in 0..12 {
+            let s = &BLAKE2B_SIGMA[round % 10];
+            blake2b_mix(&mut v, 0, 4, 8, 12, m[s[0]], m[s[1]]);
+            blake2b_mix(&mut v, 1, 5, 9, 13, m[s[2]], m[s[3]]);
+            blake2b_mix(&mut v, 2, 6, 10, 14, m[s[4]], m[s[5]]);
+            blake2b_mix(&mut v, 3, 7, 11, 15, m[s[6]], m[s[7]]);
+            blake2b_mix(&mut v, 0, 5, 10, 15, m[s[8]], m[s[9]]);
+            blake2b_mix(&mut v, 1, 6, 11, 12, m[s[10]], m[s[11]]);
+            blake2b_mix(&mut v, 2, 7, 8, 13, m[s[12]], m[s[13]]);
+            blake2b_mix(&mut v, 3, 4, 9, 14, m[s[14]], m[s[15]]);
+        }
+
+        for i in 0..8 {
+            self.state[i] ^= v[i] ^ v[i + 8];
+        }
+    }
+}
+
+// Função G do BLAKE2b
+fn blake2b_mix(v: &mut [u64; 16], a: usize, b: usize, c: usize, d: usize, x: u64, y: u64) {
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+    v[d] = (v[d] ^ v[a]).rotate_right(32);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(24);
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+    v[d] = (v[d] ^ v[a]).rotate_right(16);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(63);
+}
+
+// ====================================================================
+// CRC-32 (IEEE 802.3, polinômio refletido 0xEDB88320)
+// ====================================================================
+
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+// Atualiza um CRC em andamento (já invertido no início e ainda não no fim)
+fn crc32_update(mut crc: u32, data: &[u8]) -> u32 {
+    for &byte in data {
+        crc = CRC32_TABLE[((crc ^ byte as u32) & 0xff) as usize] ^ (crc >> 8);
+    }
+    crc
+}
+
+// ====================================================================
+// xxHash64
+// ====================================================================
+
+const XXH_PRIME64_1: u64 = 0x9e37_79b1_85eb_ca87;
+const XXH_PRIME64_2: u64 = 0xc2b2_ae3d_27d4_eb4f;
+const XXH_PRIME64_3: u64 = 0x1656_67b1_9e37_79f9;
+const XXH_PRIME64_4: u64 = 0x85eb_ca77_c2b2_ae63;
+const XXH_PRIME64_5: u64 = 0x27d4_eb2f_1656_67c5;
+
+#[derive(Debug, Clone)]
+struct XxHash64 {
+    accumulators: [u64; 4],
+    buffer: [u8; 32],
+    buffer_len: usize,
+    length: u64,
+}
+
+fn xxh64_round(accumulator: u64, input: u64) -> u64 {
+    accumulator
+        .wrapping_add(input.wrapping_mul(XXH_PRIME64_2))
+        .rotate_left(31)
+        .wrapping_mul(XXH_PRIME64_1)
+}
+
+fn xxh64_merge(hash: u64, accumulator: u64) -> u64 {
+    (hash ^ xxh64_round(0, accumulator)).wrapping_mul(XXH_PRIME64_1).wrapping_add(XXH_PRIME64_4)
+}
+
+fn read_u64(bytes: &[u8]) -> u64 {
+    u64::from_le_bytes(bytes[..8].try_into().expect("8 bytes"))
+}
+
+impl XxHash64 {
+    fn new() -> Self {
+        Self {
+            accumulators: [
+                XXH_PRIME64_1.wrapping_add(XXH_PRIME64_2),
+                XXH_PRIME64_2,
+                0,
+                XXH_PRIME64_1.wrapping_neg(),
+            ],
+            buffer: [0; 32],
+            buffer_len: 0,
+            length: 0,
+        }
+    }
+
+    fn stripe(&mut self, stripe: &[u8]) {
+        for (i, accumulator) in self.accumulators.iter_mut().enumerate() {
+            *accumulator = xxh64_round(*accumulator, read_u64(&stripe[i * 8..]));
+        }
+    }
+
+    fn update(&mut self, mut data: &[u8]) {
+        self.length += data.len() as u64;
+        if self.buffer_len > 0 {
+            let take = (32 - self.buffer_len).min(data.len());
+            self.buffer[self.buffer_len..self.buffer_len + take].copy_from_slice(&data[..take]);
+            self.buffer_len += take;
+            data = &data[take..];
+            if self.buffer_len < 32 {
+                return;
+            }
+            let stripe = self.buffer;
+            self.stripe(&stripe);
+            self.buffer_len = 0;
+        }
+
+        let mut stripes = data.chunks_exact(32);
+        for stripe in &mut stripes {
+            self.stripe(stripe);
+        }
+        let rest = stripes.remainder();
+        self.buffer[..rest.len()].copy_from_slice(rest);
+        self.buffer_len = rest.len();
+    }
+
+    fn finalize(self) -> u64 {
+        let [v1, v2, v3, v4] = self.accumulators;
+        let mut hash = if self.length >= 32 {
+            let hash = v1.rotate_left(1).wrapping_add(v2.rotate_left(7)).wrapping_add(v3.rotate_left(12)).wrapping_add(v4.rotate_left(18));
+            self.accumulators.iter().fold(hash, |hash, &accumulator| xxh64_merge(hash, accumulator))
+        } else {
+            XXH_PRIME64_5
+        };
+        hash = hash.wrapping_add(self.length);
+
+        let mut rest = &self.buffer[..self.buffer_len];
+        while rest.len() >= 8 {
+            hash ^= xxh64_round(0, read_u64(rest));
+            hash = hash.rotate_left(27).wrapping_mul(XXH_PRIME64_1).wrapping_add(XXH_PRIME64_4);
+            rest = &rest[8..];
+        }
+        if rest.len() >= 4 {
+            let word = u32::from_le_bytes(rest[..4].try_into().expect("4 bytes")) as u64;
+            hash ^= word.wrapping_mul(XXH_PRIME64_1);
+            hash = hash.rotate_left(23).wrapping_mul(XXH_PRIME64_2).wrapping_add(XXH_PRIME64_3);
+            rest = &rest[4..];
+        }
+        for &byte in rest {
+            hash ^= (byte as u64).wrapping_mul(XXH_PRIME64_5);
+            hash = hash.rotate_left(11).wrapping_mul(XXH_PRIME64_1);
+        }
+
+        hash ^= hash >> 33;
+        hash = hash.wrapping_mul(XXH_PRIME64_2);
+        hash ^= hash >> 29;
+        hash = hash.wrapping_mul(XXH_PRIME64_3);
+        hash ^ (hash >> 32)
+    }
+}
+
+// ====================================================================
+// TESTES UNITÁRIOS
+// ====================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(unix)]
+    use crate::tests::make_fifo;
+    use crate::tests::scratch_dir;
+    use crate::{Archivus, FileFilter};
+    use std::fs;
+
+    fn hex(algorithm: HashAlgorithm, data: &[u8]) -> String {
+        ContentHasher::digest(algorithm, data).to_hex()
+    }
+
+    // Resultado igual ao de uma única chamada, com blocos de tamanhos variados
+    fn chunked(algorithm: HashAlgorithm, data: &[u8]) -> String {
+        let mut hasher = ContentHasher::new(algorithm);
+        let mut rest = data;
+        let mut size = 1;
+        while !rest.is_empty() {
+            let take = size.min(rest.len());
+            hasher.update(&rest[..take]);
+            rest = &rest[take..];
+            size = size * 3 % 97 + 1;
+        }
+        hasher.finalize().to_hex()
+    }
+
+    #[test]
+    fn test_hash_vectors() {
+        use HashAlgorithm::*;
+
+        // Referências: sha256sum, b2sum, zlib.crc32 e xxhsum
+        assert_eq!(hex(Sha256, b""), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+        assert_eq!(hex(Sha256, b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"), "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1");
+        assert_eq!(hex(Blake2b, b""), "786a02f742015903c6c6fd852552d272912f4740e15847618a86e217f71f5419d25e1031afee585313896444934eb04b903a685b1448b755d56f701afe9be2ce");
+        assert_eq!(hex(Blake2b, b"abc"), "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d17d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923");
+        assert_eq!(hex(Crc32, b""), "00000000");
+        assert_eq!(hex(Crc32, b"123456789"), "cbf43926");
+        assert_eq!(hex(XxHash64, b""), "ef46db3751d8e999");
+        assert_eq!(hex(XxHash64, b"abc"), "44bc2cf5ad770999");
+
+        let data: Vec<u8> = (0..10_000u32).map(|i| (i * 7 + i / 251) as u8).collect();
+        assert_eq!(hex(Sha256, &data), "ee210c4f460bea8d53dc48e08dcecdcca8b49c25887bd589816c5b2a870f4d25");
+        assert_eq!(hex(Blake2b, &data), "2285d16a08343fe27ab427b1d70015565ebe9e21b4708e3698a23cf9a3c9135c3e9e62d7a3ce8ac722d519c5010c58d69ae09e2c7a9596762dd3e575b2af854f");
+        assert_eq!(hex(Crc32, &data), "9e51449e");
+        assert_eq!(hex(XxHash64, &data), "ea554c71063fdeb2");
+        for algorithm in HashAlgorithm::ALL {
+            assert_eq!(chunked(algorithm, &data), hex(algorithm, &data), "{}", algorithm);
+            assert_eq!(ContentHasher::digest(algorithm, &data).as_bytes().len(), algorithm.digest_len());
+        }
+    }
+
+    #[test]
+    fn test_digest_encoding() {
+        let digest = |bytes: &[u8]| Digest { algorithm: HashAlgorithm::Crc32, bytes: bytes.to_vec() };
+        assert_eq!(digest(b"").to_base64(), "");
+        assert_eq!(digest(b"f").to_base64(), "Zg==");
+        assert_eq!(digest(b"fo").to_base64(), "Zm8=");
+        assert_eq!(digest(b"foobar").to_base64(), "Zm9vYmFy");
+        assert_eq!(digest(&[0xcb, 0xf4, 0x39, 0x26]).to_string(), "cbf43926");
+        assert_eq!(Digest::from_hex(HashAlgorithm::Crc32, "CBF43926").unwrap(), digest(&[0xcb, 0xf4, 0x39, 0x26]));
+        assert!(Digest::from_hex(HashAlgorithm::Crc32, "cbf4392").is_err());
+        assert!(Digest::from_hex(HashAlgorithm::Crc32, "cbf4392g").is_err());
+
+        assert_eq!("SHA-256".parse::<HashAlgorithm>().unwrap(), HashAlgorithm::Sha256);
+        assert_eq!("xxh64".parse::<HashAlgorithm>().unwrap().to_string(), "xxh64");
+        assert!("md5".parse::<HashAlgorithm>().is_err());
+    }
+
+    #[test]
+    fn test_hash_tree() {
+        let utils = Archivus::new();
+        let root = scratch_dir("hash_tree");
+        fs::create_dir_all(root.join("sub")).unwrap();
+        fs::create_dir_all(root.join("vazio")).unwrap();
+        fs::write(root.join("b.txt"), "abc").unwrap();
+        fs::write(root.join("sub/a.txt"), "").unwrap();
+
+        let digest = utils.hash_file(root.join("b.txt"), HashAlgorithm::Sha256).unwrap();
+        assert_eq!(digest.to_hex(), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        assert_eq!(digest.to_base64(), "ungWv48Bz+pBQUDeXa4iI7ADYaOWF3qctBD/YfIAFa0=");
+        assert_eq!(utils.hash_file(root.join("b.txt"), HashAlgorithm::Crc32).unwrap().to_hex(), "352441c2");
+
+        let filter = FileFilter {
+            recursive: true,
+            ..Default::default()
+        };
+        let hashes = utils.hash_files(&root, HashAlgorithm::XxHash64, &filter).unwrap();
+        assert_eq!(hashes.len(), 2);
+        assert_eq!(hashes[0].file.name, "b.txt");
+        assert_eq!(hashes[1].digest.to_hex(), "ef46db3751d8e999");
+
+        // Mesmo hash de `sha256sum b.txt sub/a.txt | sha256sum`
+        let manifest = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  b.txt\n\
+                        e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855  sub/a.txt\n";
+        let tree = utils.hash_tree(&root, HashAlgorithm::Sha256, &filter).unwrap();
+        assert_eq!(tree, ContentHasher::digest(HashAlgorithm::Sha256, manifest.as_bytes()));
+
+        // O conteúdo altera o hash da árvore; diretórios vazios, não
+        fs::remove_dir(root.join("vazio")).unwrap();
+        assert_eq!(utils.hash_tree(&root, HashAlgorithm::Sha256, &filter).unwrap(), tree);
+
+        // Pipes e links quebrados não são lidos nem entram no hash
+        #[cfg(unix)]
+        {
+            make_fifo(&root.join("pipe"));
+            std::os::unix::fs::symlink(root.join("inexistente"), root.join("quebrado")).unwrap();
+            assert_eq!(utils.hash_files(&root, HashAlgorithm::XxHash64, &filter).unwrap().len(), 2);
+            assert_eq!(utils.hash_tree(&root, HashAlgorithm::Sha256, &filter).unwrap(), tree);
+            fs::remove_file(root.join("pipe")).unwrap();
+            fs::remove_file(root.join("quebrado")).unwrap();
+        }
+
+        fs::write(root.join("sub/a.txt"), "x").unwrap();
+        assert_ne!(utils.hash_tree(&root, HashAlgorithm::Sha256, &filter).unwrap(), tree);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
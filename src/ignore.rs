@@ -0,0 +1,184 @@
+// ====================================================================
+// ARQUIVOS DE EXCLUSÃO (.gitignore, .ignore, .archivusignore)
+// ====================================================================
+// Regras no formato do .gitignore, lidas em cada nível da árvore e
+// aplicadas durante o percurso
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::glob::{path_segments, Glob};
+
+/// Arquivos de exclusão lidos em cada diretório, do menos para o mais prioritário
+pub(crate) const IGNORE_FILE_NAMES: &[&str] = &[".gitignore", ".ignore", ".archivusignore"];
+
+// Uma linha de um arquivo de exclusão
+#[derive(Debug, Clone)]
+struct IgnoreRule {
+    glob: Glob,
+    /// `!padrão`: inclui de volta o que uma regra anterior excluiu
+    negated: bool,
+    /// `padrão/`: vale apenas para diretórios
+    dir_only: bool,
+}
+
+impl IgnoreRule {
+    fn parse(line: &str) -> Option<Self> {
+        let line = trim_trailing_spaces(line);
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+
+        // `\#` e `\!` no início são tratados como literais pelo próprio glob
+        let (negated, line) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+
+        let (dir_only, line) = match line.strip_suffix('/') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        if line.is_empty() {
+            return None;
+        }
+
+        // Padrões com barra no início ou no meio são relativos ao diretório
+        // do arquivo; os demais valem em qualquer nível abaixo dele
+        let glob = match line.strip_prefix('/') {
+            Some(anchored) => Glob::gitignore(anchored),
+            None if line.contains('/') => Glob::gitignore(line),
+            None => Glob::gitignore(&format!("**/{}", line)),
+        };
+
+        Some(Self { glob, negated, dir_only })
+    }
+}
+
+// Remove espaços finais, exceto os escapados com `\`
+fn trim_trailing_spaces(line: &str) -> &str {
+    let mut end = line.len();
+    while line[..end].ends_with(' ') && !line[..end - 1].ends_with('\\') {
+        end -= 1;
+    }
+    &line[..end]
+}
+
+/// Conjunto de regras relativas a um diretório base
+#[derive(Debug, Clone)]
+pub(crate) struct IgnoreRules {
+    base: PathBuf,
+    rules: Vec<IgnoreRule>,
+}
+
+impl IgnoreRules {
+    pub(crate) fn new<I, S>(base: &Path, lines: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            base: base.to_path_buf(),
+            rules: lines.into_iter().filter_map(|line| IgnoreRule::parse(line.as_ref())).collect(),
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Retorna `Some(true)` se o caminho é excluído, `Some(false)` se é
+    /// incluído de volta por uma negação e `None` se nenhuma regra se aplica.
+    /// Como no Git, a última regra que corresponde prevalece.
+    /// Caminhos com componentes que não são UTF-8 válido nunca correspondem.
+    pub(crate) fn matched(&self, path: &Path, is_dir: bool) -> Option<bool> {
+        let segments = path_segments(path.strip_prefix(&self.base).ok()?)?;
+
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| (is_dir || !rule.dir_only) && rule.glob.matches_segments(&segments))
+            .map(|rule| !rule.negated)
+    }
+}
+
+/// Regras de um diretório, encadeadas com as dos diretórios acima dele
+#[derive(Debug)]
+pub(crate) struct IgnoreLevel {
+    rules: IgnoreRules,
+    parent: Option<Arc<IgnoreLevel>>,
+}
+
+impl IgnoreLevel {
+    /// Lê os arquivos de exclusão de `dir`. Sem nenhuma regra nova, o nível
+    /// do diretório pai é reaproveitado.
+    pub(crate) fn load(dir: &Path, parent: Option<Arc<IgnoreLevel>>) -> Option<Arc<IgnoreLevel>> {
+        let lines: Vec<String> = IGNORE_FILE_NAMES
+            .iter()
+            .filter_map(|name| fs::read_to_string(dir.join(name)).ok())
+            .flat_map(|content| content.lines().map(str::to_string).collect::<Vec<_>>())
+            .collect();
+
+        let rules = IgnoreRules::new(dir, lines);
+        if rules.is_empty() {
+            return parent;
+        }
+
+        Some(Arc::new(IgnoreLevel { rules, parent }))
+    }
+
+    /// Consulta as regras do diretório mais próximo para o mais distante
+    pub(crate) fn matched(&self, path: &Path, is_dir: bool) -> Option<bool> {
+        let mut level = Some(self);
+        while let Some(current) = level {
+            if let Some(ignored) = current.rules.matched(path, is_dir) {
+                return Some(ignored);
+            }
+            level = current.parent.as_deref();
+        }
+        None
+    }
+}
+
+// ====================================================================
+// TESTES UNITÁRIOS
+// ====================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ignored(rules: &str, path: &str, is_dir: bool) -> Option<bool> {
+        IgnoreRules::new(Path::new("raiz"), rules.lines()).matched(&Path::new("raiz").join(path), is_dir)
+    }
+
+    #[test]
+    fn test_ignore_rules() {
+        let rules = "# comentário\n*.log\n!importante.log\n/build\ncache/\ndocs/*.tmp\n\\#literal\n";
+
+        assert_eq!(ignored(rules, "a/b/erro.log", false), Some(true));
+        assert_eq!(ignored(rules, "a/importante.log", false), Some(false));
+        assert_eq!(ignored(rules, "build", true), Some(true));
+        assert_eq!(ignored(rules, "src/build", true), None);
+        assert_eq!(ignored(rules, "x/cache", true), Some(true));
+        assert_eq!(ignored(rules, "x/cache", false), None);
+        assert_eq!(ignored(rules, "docs/a.tmp", false), Some(true));
+        assert_eq!(ignored(rules, "docs/sub/a.tmp", false), None);
+        assert_eq!(ignored(rules, "#literal", false), Some(true));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_ignore_non_utf8() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        // Um componente inválido não pode ser descartado: `/b/c.txt` não
+        // corresponde a `\xff/b/c.txt`
+        let rules = IgnoreRules::new(Path::new("raiz"), ["/b/c.txt", "*.txt"]);
+        let path = Path::new("raiz").join(OsStr::from_bytes(b"\xff")).join("b/c.txt");
+        assert_eq!(rules.matched(&path, false), None);
+        assert_eq!(rules.matched(Path::new("raiz/b/c.txt"), false), Some(true));
+    }
+}
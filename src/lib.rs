@@ -6,8 +6,45 @@
 
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::io::{self};
-use std::collections::HashMap;
+use std::io::{self, Write};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+mod diff;
+mod duplicates;
+mod glob;
+mod hash;
+mod ignore;
+mod manifest;
+mod metadata;
+mod mime;
+mod parallel;
+mod pattern;
+mod predicate;
+mod query;
+mod regex;
+mod search;
+mod snapshot;
+mod sync;
+mod walk;
+
+pub use diff::{Change, CompareMode, DiffOptions, DiffReport, DiffSource};
+pub use duplicates::{DuplicateGroup, DuplicateReport};
+pub use glob::Glob;
+pub use hash::{ContentHasher, Digest, FileHash, HashAlgorithm};
+pub use manifest::{parse_manifest, ManifestEntry, ManifestFormat, ManifestReport};
+pub use metadata::{FileType, InsecureFile, ModeFilter, PermissionIssue, Timestamp, UnixMetadata};
+pub use mime::{detect_mime, extensions_for_mime, mime_for_extension, ExtensionMismatch, MIME_BINARY, MIME_EMPTY, MIME_TEXT};
+pub use parallel::ParallelWalker;
+pub use pattern::Pattern;
+pub use predicate::{And, Criterion, IntoFilter, Not, Or, Predicate};
+pub use regex::Regex;
+pub use search::{ContentMatch, SearchOptions, SearchPattern};
+pub use snapshot::{Snapshot, SnapshotEntry};
+pub use sync::{SyncAction, SyncOptions, SyncReport};
+pub use walk::{SymlinkPolicy, Walker};
+use walk::Entries;
 
 // ====================================================================
 // ESTRUTURAS DE DADOS E ENUMS
@@ -26,6 +63,10 @@ pub enum ArchivusError {
     InvalidExtension(String),
     /// Caminho inválido
     InvalidPath(String),
+    /// Padrão de busca inválido
+    InvalidPattern(String),
+    /// Erro de sintaxe em uma consulta, com a posição (em caracteres) do trecho inválido
+    ParseError { position: usize, message: String },
 }
 
 impl std::fmt::Display for ArchivusError {
@@ -36,6 +77,10 @@ impl std::fmt::Display for ArchivusError {
             ArchivusError::IoError(msg) => write!(f, "Erro de I/O: {}", msg),
             ArchivusError::InvalidExtension(msg) => write!(f, "Extensão inválida: {}", msg),
             ArchivusError::InvalidPath(msg) => write!(f, "Caminho inválido: {}", msg),
+            ArchivusError::InvalidPattern(msg) => write!(f, "Padrão inválido: {}", msg),
+            ArchivusError::ParseError { position, message } => {
+                write!(f, "Erro de sintaxe na posição {}: {}", position, message)
+            }
         }
     }
 }
@@ -67,29 +112,80 @@ pub struct FileInfo {
     pub is_directory: bool,
     /// Se é um arquivo
     pub is_file: bool,
+    /// Se o caminho é um link simbólico
+    pub is_symlink: bool,
+    /// Tipo do item, segundo os mesmos metadados de `is_file` e `is_directory`
+    pub file_type: FileType,
+    /// Se o item é oculto (nome iniciado por `.` ou, no Windows, com o
+    /// atributo de oculto)
+    pub is_hidden: bool,
+    /// Destino do link simbólico, exatamente como gravado no link
+    pub link_target: Option<PathBuf>,
+    /// Identificador do dispositivo (sistema de arquivos) em que o item está;
+    /// disponível apenas em sistemas Unix
+    pub device: Option<u64>,
     /// Última modificação (timestamp Unix)
     pub modified: Option<u64>,
+    /// Último acesso (timestamp Unix)
+    pub accessed: Option<u64>,
+    /// Criação (timestamp Unix), se o sistema de arquivos registrar
+    pub created: Option<u64>,
+    /// Metadados de sistemas Unix (permissões, dono, inode, blocos e datas
+    /// com nanossegundos); `None` em outras plataformas
+    pub unix: Option<UnixMetadata>,
+    /// Profundidade em relação à raiz da busca (1 = conteúdo direto da raiz,
+    /// 0 quando criado diretamente com `FileInfo::new`)
+    pub depth: usize,
 }
 
 impl FileInfo {
     /// Cria um novo FileInfo a partir de um caminho
+    ///
+    /// Links simbólicos são seguidos: tamanho e tipo descrevem o destino do
+    /// link. Se o destino não existir, os metadados do próprio link são usados.
     pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, ArchivusError> {
-        let path = path.as_ref();
-        let metadata = fs::metadata(path)?;
+        Self::from_path(path.as_ref(), true)
+    }
+
+    /// Cria um novo FileInfo sem seguir links simbólicos
+    ///
+    /// Para um link, `is_file` e `is_directory` são falsos e `size` é o
+    /// tamanho do próprio link.
+    pub fn new_no_follow<P: AsRef<Path>>(path: P) -> Result<Self, ArchivusError> {
+        Self::from_path(path.as_ref(), false)
+    }
+
+    fn from_path(path: &Path, follow_links: bool) -> Result<Self, ArchivusError> {
+        let link_metadata = fs::symlink_metadata(path)?;
+        let is_symlink = link_metadata.file_type().is_symlink();
 
         let name = path.file_name()
             .and_then(|n| n.to_str())
             .unwrap_or("")
             .to_string();
+        let is_hidden = is_hidden(&name, &link_metadata);
+
+        let link_target = if is_symlink {
+            fs::read_link(path).ok()
+        } else {
+            None
+        };
+
+        let metadata = if is_symlink && follow_links {
+            fs::metadata(path).unwrap_or(link_metadata)
+        } else {
+            link_metadata
+        };
 
         let extension = path.extension()
             .and_then(|ext| ext.to_str())
             .map(|s| s.to_lowercase());
 
-        let modified = metadata.modified()
-            .ok()
-            .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
-            .map(|duration| duration.as_secs());
+        let device = device_id(&metadata);
+        let unix = UnixMetadata::from_metadata(&metadata);
+        let modified = unix_seconds(metadata.modified());
+        let accessed = unix_seconds(metadata.accessed());
+        let created = unix_seconds(metadata.created());
 
         Ok(FileInfo {
             path: path.to_path_buf(),
@@ -98,37 +194,371 @@ impl FileInfo {
             size: metadata.len(),
             is_directory: metadata.is_dir(),
             is_file: metadata.is_file(),
+            is_symlink,
+            file_type: FileType::from_std(metadata.file_type()),
+            is_hidden,
+            link_target,
+            device,
             modified,
+            accessed,
+            created,
+            unix,
+            depth: 0,
         })
     }
 }
 
+// Itens ocultos: nome iniciado por ponto e, no Windows, o atributo de oculto
+#[cfg(windows)]
+fn is_hidden(name: &str, metadata: &fs::Metadata) -> bool {
+    use std::os::windows::fs::MetadataExt;
+    const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+    name.starts_with('.') || metadata.file_attributes() & FILE_ATTRIBUTE_HIDDEN != 0
+}
+
+#[cfg(not(windows))]
+fn is_hidden(name: &str, _metadata: &fs::Metadata) -> bool {
+    name.starts_with('.')
+}
+
+// Dispositivo em que o item está, usado para não atravessar pontos de montagem
+#[cfg(unix)]
+pub(crate) fn device_id(metadata: &fs::Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Some(metadata.dev())
+}
+
+#[cfg(not(unix))]
+pub(crate) fn device_id(_metadata: &fs::Metadata) -> Option<u64> {
+    None
+}
+
+// Converte um instante dos metadados em timestamp Unix, se disponível
+fn unix_seconds(time: io::Result<SystemTime>) -> Option<u64> {
+    time.ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_secs())
+}
+
+/// Instante usado nos filtros de data
+///
+/// Pode ser absoluto (timestamp Unix) ou relativo ao momento da comparação,
+/// como "30 dias atrás".
+///
+/// # Exemplos
+/// ```rust
+/// use archivus::{FileFilter, TimeSpec};
+///
+/// // Arquivos sem modificação há mais de 30 dias
+/// let filter = FileFilter {
+///     modified_before: Some(TimeSpec::days_ago(30)),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeSpec {
+    /// Timestamp Unix, em segundos
+    At(u64),
+    /// Intervalo antes do momento da comparação
+    Ago(Duration),
+}
+
+impl TimeSpec {
+    /// Instante `days` dias antes do momento da comparação; valores grandes
+    /// demais são limitados ao maior intervalo representável
+    pub fn days_ago(days: u64) -> Self {
+        TimeSpec::Ago(Duration::from_secs(days.saturating_mul(24 * 60 * 60)))
+    }
+
+    /// Instante `hours` horas antes do momento da comparação
+    pub fn hours_ago(hours: u64) -> Self {
+        TimeSpec::Ago(Duration::from_secs(hours.saturating_mul(60 * 60)))
+    }
+
+    /// Converte para timestamp Unix, tomando `now` como o momento atual
+    pub fn resolve(&self, now: u64) -> u64 {
+        match self {
+            TimeSpec::At(timestamp) => *timestamp,
+            TimeSpec::Ago(duration) => now.saturating_sub(duration.as_secs()),
+        }
+    }
+}
+
+impl From<SystemTime> for TimeSpec {
+    fn from(time: SystemTime) -> Self {
+        TimeSpec::At(unix_seconds(Ok(time)).unwrap_or(0))
+    }
+}
+
 /// Filtros para busca de arquivos
 #[derive(Debug, Clone)]
 pub struct FileFilter {
     /// Extensões permitidas (None = todas)
     pub extensions: Option<Vec<String>>,
+    /// Extensões excluídas, aplicadas após `extensions`
+    pub exclude_extensions: Option<Vec<String>>,
     /// Tamanho mínimo em bytes
     pub min_size: Option<u64>,
     /// Tamanho máximo em bytes
     pub max_size: Option<u64>,
+    /// Critério sobre os bits de permissão; itens sem metadados Unix não são
+    /// incluídos
+    pub mode: Option<ModeFilter>,
+    /// Apenas itens deste dono (uid); itens sem metadados Unix não são incluídos
+    pub uid: Option<u32>,
+    /// Apenas itens deste grupo (gid); itens sem metadados Unix não são incluídos
+    pub gid: Option<u32>,
     /// Incluir diretórios
     pub include_directories: bool,
     /// Incluir arquivos
     pub include_files: bool,
     /// Busca recursiva
     pub recursive: bool,
+    /// Incluir itens ocultos; sem eles, diretórios ocultos também não são
+    /// percorridos
+    pub include_hidden: bool,
+    /// Profundidade mínima dos itens retornados (1 = conteúdo direto da raiz)
+    pub min_depth: Option<usize>,
+    /// Profundidade máxima percorrida na busca recursiva
+    pub max_depth: Option<usize>,
+    /// Como links simbólicos são tratados no percurso
+    pub symlinks: SymlinkPolicy,
+    /// Não atravessa pontos de montagem: diretórios em outro sistema de
+    /// arquivos que a raiz são produzidos, mas não percorridos (apenas Unix)
+    pub same_filesystem: bool,
+    /// Número de threads do percurso (1 = sequencial, 0 = uma por núcleo)
+    pub threads: usize,
+    /// Produz os itens em ordem de caminho, independente do número de threads
+    pub sorted: bool,
+    /// Respeita `.gitignore`, `.ignore` e `.archivusignore` em cada nível da árvore
+    pub respect_ignore_files: bool,
+    /// Padrões no formato do `.gitignore`, relativos à raiz da busca, aplicados
+    /// em toda a árvore com prioridade menor que a dos arquivos de exclusão
+    pub global_ignore: Option<Vec<String>>,
+    /// Inclui apenas itens cujo nome corresponde a um dos padrões
+    pub names: Option<Vec<Pattern>>,
+    /// Exclui itens cujo nome corresponde a um dos padrões; diretórios
+    /// excluídos continuam sendo percorridos
+    pub exclude_names: Option<Vec<Pattern>>,
+    /// Diretórios cujo nome corresponde a um dos padrões não são produzidos
+    /// nem abertos na busca recursiva (ex.: `.git`, `node_modules`)
+    pub prune_directories: Option<Vec<Pattern>>,
+    /// Inclui apenas itens cujo nome corresponde à expressão
+    pub name_regex: Option<Regex>,
+    /// Exclui itens cujo nome corresponde à expressão
+    pub exclude_name_regex: Option<Regex>,
+    /// Inclui apenas itens cujo caminho (como produzido pela busca, a partir
+    /// da raiz informada) corresponde à expressão
+    pub path_regex: Option<Regex>,
+    /// Exclui itens cujo caminho corresponde à expressão
+    pub exclude_path_regex: Option<Regex>,
+    /// Inclui apenas arquivos cujo tipo MIME, detectado pelo conteúdo,
+    /// corresponde a um dos padrões (ex.: `image/*`); diretórios passam
+    pub mime_types: Option<Vec<Pattern>>,
+    /// Modificados neste instante ou depois
+    pub modified_after: Option<TimeSpec>,
+    /// Modificados antes deste instante
+    pub modified_before: Option<TimeSpec>,
+    /// Acessados neste instante ou depois
+    pub accessed_after: Option<TimeSpec>,
+    /// Acessados antes deste instante
+    pub accessed_before: Option<TimeSpec>,
+    /// Criados neste instante ou depois
+    pub created_after: Option<TimeSpec>,
+    /// Criados antes deste instante
+    pub created_before: Option<TimeSpec>,
+    /// Critério adicional, combinado com os demais campos com E
+    pub predicate: Option<Arc<dyn Predicate>>,
+}
+
+impl FileFilter {
+    /// Verifica se um FileInfo corresponde ao filtro
+    pub fn matches(&self, file_info: &FileInfo) -> bool {
+        // Verifica tipo (arquivo/diretório)
+        if file_info.is_file && !self.include_files {
+            return false;
+        }
+        if file_info.is_directory && !self.include_directories {
+            return false;
+        }
+
+        // Verifica itens ocultos
+        if file_info.is_hidden && !self.include_hidden {
+            return false;
+        }
+
+        // Verifica profundidade
+        if let Some(min_depth) = self.min_depth
+            && file_info.depth < min_depth
+        {
+            return false;
+        }
+        if let Some(max_depth) = self.max_depth
+            && file_info.depth > max_depth
+        {
+            return false;
+        }
+
+        // Verifica extensão
+        if let Some(ref allowed_extensions) = self.extensions
+            && file_info.is_file
+        {
+            match &file_info.extension {
+                Some(ext) => {
+                    if !allowed_extensions.iter().any(|allowed| allowed.to_lowercase() == ext.to_lowercase()) {
+                        return false;
+                    }
+                }
+                None => return false,
+            }
+        }
+
+        // Verifica extensões excluídas
+        if let Some(ref excluded_extensions) = self.exclude_extensions
+            && file_info.is_file
+            && let Some(ref ext) = file_info.extension
+            && excluded_extensions.iter().any(|excluded| excluded.eq_ignore_ascii_case(ext))
+        {
+            return false;
+        }
+
+        // Verifica tamanho mínimo
+        if let Some(min_size) = self.min_size
+            && file_info.size < min_size
+        {
+            return false;
+        }
+
+        // Verifica tamanho máximo
+        if let Some(max_size) = self.max_size
+            && file_info.size > max_size
+        {
+            return false;
+        }
+
+        // Verifica permissões, dono e grupo
+        if self.mode.is_some() || self.uid.is_some() || self.gid.is_some() {
+            let Some(unix) = &file_info.unix else {
+                return false;
+            };
+            if self.mode.is_some_and(|mode| !mode.matches(unix.permissions()))
+                || self.uid.is_some_and(|uid| unix.uid != uid)
+                || self.gid.is_some_and(|gid| unix.gid != gid)
+            {
+                return false;
+            }
+        }
+
+        // Verifica padrões de nome
+        if let Some(ref names) = self.names
+            && !names.iter().any(|pattern| pattern.matches(&file_info.name))
+        {
+            return false;
+        }
+        if let Some(ref excluded_names) = self.exclude_names
+            && excluded_names.iter().any(|pattern| pattern.matches(&file_info.name))
+        {
+            return false;
+        }
+
+        // Verifica expressões regulares de nome e caminho
+        if self.name_regex.as_ref().is_some_and(|regex| !regex.is_match(&file_info.name))
+            || self.exclude_name_regex.as_ref().is_some_and(|regex| regex.is_match(&file_info.name))
+        {
+            return false;
+        }
+        if self.path_regex.is_some() || self.exclude_path_regex.is_some() {
+            let path = file_info.path.to_string_lossy();
+            if self.path_regex.as_ref().is_some_and(|regex| !regex.is_match(&path))
+                || self.exclude_path_regex.as_ref().is_some_and(|regex| regex.is_match(&path))
+            {
+                return false;
+            }
+        }
+
+        // Verifica datas de modificação, acesso e criação
+        let time_ranges = [
+            (file_info.modified, self.modified_after, self.modified_before),
+            (file_info.accessed, self.accessed_after, self.accessed_before),
+            (file_info.created, self.created_after, self.created_before),
+        ];
+        if time_ranges.iter().any(|(_, after, before)| after.is_some() || before.is_some()) {
+            let now = unix_seconds(Ok(SystemTime::now())).unwrap_or(0);
+            for (time, after, before) in time_ranges {
+                if after.is_none() && before.is_none() {
+                    continue;
+                }
+                // Sem a data no sistema de arquivos, o item não é incluído
+                let Some(time) = time else {
+                    return false;
+                };
+                if after.is_some_and(|after| time < after.resolve(now))
+                    || before.is_some_and(|before| time >= before.resolve(now))
+                {
+                    return false;
+                }
+            }
+        }
+
+        // Verifica o tipo detectado pelo conteúdo; arquivos que não podem ser
+        // lidos não são incluídos
+        if let Some(ref mime_types) = self.mime_types
+            && file_info.is_file
+        {
+            match mime::sniff_file(&file_info.path) {
+                Ok(mime) if mime_types.iter().any(|pattern| pattern.matches(mime)) => {}
+                _ => return false,
+            }
+        }
+
+        // Verifica o predicado adicional
+        if let Some(predicate) = &self.predicate
+            && !predicate.matches(file_info)
+        {
+            return false;
+        }
+
+        true
+    }
 }
 
 impl Default for FileFilter {
     fn default() -> Self {
         Self {
             extensions: None,
+            exclude_extensions: None,
             min_size: None,
             max_size: None,
+            mode: None,
+            uid: None,
+            gid: None,
             include_directories: true,
             include_files: true,
             recursive: false,
+            include_hidden: true,
+            min_depth: None,
+            max_depth: None,
+            symlinks: SymlinkPolicy::default(),
+            same_filesystem: false,
+            threads: 1,
+            sorted: false,
+            respect_ignore_files: false,
+            global_ignore: None,
+            names: None,
+            exclude_names: None,
+            prune_directories: None,
+            name_regex: None,
+            exclude_name_regex: None,
+            path_regex: None,
+            exclude_path_regex: None,
+            mime_types: None,
+            modified_after: None,
+            modified_before: None,
+            accessed_after: None,
+            accessed_before: None,
+            created_after: None,
+            created_before: None,
+            predicate: None,
         }
     }
 }
@@ -204,7 +634,8 @@ impl Archivus {
     /// * `false` - Se o caminho não existe OU é um arquivo
     ///
     /// # Exemplos
-    /// ```rust
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), archivus::ArchivusError> {
     /// use archivus::Archivus;
     ///
     /// let utils = Archivus::new();
@@ -222,14 +653,21 @@ impl Archivus {
     ///     // Precisa criar o diretório primeiro
     ///     utils.create_directory("output")?;
     /// }
+    /// # Ok(())
+    /// # }
     /// ```
     ///
     /// # Dica
     /// Combine com `create_directory()` para garantir que um diretório existe:
-    /// ```rust
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), archivus::ArchivusError> {
+    /// # use archivus::Archivus;
+    /// # let utils = Archivus::new();
     /// if !utils.directory_exists("backup") {
     ///     utils.create_directory("backup")?;
     /// }
+    /// # Ok(())
+    /// # }
     /// ```
     pub fn directory_exists<P: AsRef<Path>>(&self, path: P) -> bool {
         path.as_ref().is_dir()
@@ -329,6 +767,27 @@ impl Archivus {
             .unwrap_or(false)
     }
 
+    /// Detecta o tipo MIME de um arquivo pelo conteúdo, ignorando a extensão
+    ///
+    /// Lê apenas os primeiros 8 KB do arquivo. Veja `detect_mime` para os
+    /// formatos reconhecidos.
+    ///
+    /// # Exemplos
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), archivus::ArchivusError> {
+    /// use archivus::Archivus;
+    ///
+    /// let utils = Archivus::new();
+    /// if utils.detect_mime_type("foto.jpg")? != "image/jpeg" {
+    ///     println!("foto.jpg não é um JPEG");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn detect_mime_type<P: AsRef<Path>>(&self, path: P) -> Result<&'static str, ArchivusError> {
+        Ok(mime::sniff_file(path.as_ref())?)
+    }
+
     /// Verifica se um arquivo está vazio (tem 0 bytes)
     ///
     /// Esta função é útil para validar arquivos antes de processá-los.
@@ -350,7 +809,8 @@ impl Archivus {
     /// - Erro de I/O do sistema
     ///
     /// # Exemplos
-    /// ```rust
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), archivus::ArchivusError> {
     /// use archivus::Archivus;
     ///
     /// let utils = Archivus::new();
@@ -380,6 +840,8 @@ impl Archivus {
     /// } else {
     ///     println!("Aviso: Arquivo de dados está vazio");
     /// }
+    /// # Ok(())
+    /// # }
     /// ```
     ///
     /// # Dica de Performance
@@ -414,9 +876,13 @@ impl Archivus {
     /// - `extension`: Extensão do arquivo (sem o ponto)
     /// - `size`: Tamanho em bytes
     /// - `modified`: Timestamp da última modificação
+    /// - `accessed`/`created`: Timestamps do último acesso e da criação, se disponíveis
+    /// - `is_hidden`: Se o arquivo é oculto (incluído no resultado; para
+    ///   excluir, use `list_files_with_filter` com `include_hidden: false`)
     ///
     /// # Exemplos
-    /// ```rust
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), archivus::ArchivusError> {
     /// use archivus::Archivus;
     ///
     /// let utils = Archivus::new();
@@ -452,6 +918,8 @@ impl Archivus {
     /// let arquivos = utils.list_files("documents")?;
     /// let tamanho_total: u64 = arquivos.iter().map(|f| f.size).sum();
     /// println!("Tamanho total: {} bytes", tamanho_total);
+    /// # Ok(())
+    /// # }
     /// ```
     ///
     /// # Casos de Uso Comuns
@@ -463,19 +931,35 @@ impl Archivus {
     /// # Veja Também
     /// - `list_directories()` - Para listar apenas diretórios
     /// - `list_all()` - Para listar arquivos E diretórios
+    /// - `list_files_with_filter()` - Para aplicar um filtro, como excluir itens ocultos
     /// - `list_with_filter()` - Para busca com critérios específicos
     pub fn list_files<P: AsRef<Path>>(&self, dir_path: P) -> Result<Vec<FileInfo>, ArchivusError> {
-        let mut files = Vec::new();
-
-        for entry in fs::read_dir(dir_path)? {
-            let entry = entry?;
-            let file_info = FileInfo::new(entry.path())?;
-
-            if file_info.is_file {
-                files.push(file_info);
-            }
-        }
+        self.list_files_with_filter(dir_path, &FileFilter::default())
+    }
 
+    /// Lista apenas os arquivos que correspondem a um filtro
+    ///
+    /// Diretórios, pipes, sockets e links quebrados nunca são retornados. As
+    /// demais opções do filtro, como `include_hidden` e `recursive`, são
+    /// respeitadas.
+    ///
+    /// # Exemplos
+    /// ```rust,no_run
+    /// use archivus::{Archivus, FileFilter};
+    ///
+    /// let utils = Archivus::new();
+    /// let visible = FileFilter { include_hidden: false, ..Default::default() };
+    /// let files = utils.list_files_with_filter("/home/ana", &visible)?;
+    /// # Ok::<(), archivus::ArchivusError>(())
+    /// ```
+    pub fn list_files_with_filter<P: AsRef<Path>>(&self, dir_path: P, filter: &FileFilter) -> Result<Vec<FileInfo>, ArchivusError> {
+        let mut files = self.list_with_filter(dir_path, &FileFilter {
+            include_files: true,
+            include_directories: false,
+            ..filter.clone()
+        })?;
+        // O filtro deixa passar pipes, sockets e links quebrados
+        files.retain(|file| file.is_file);
         Ok(files)
     }
 
@@ -493,7 +977,8 @@ impl Archivus {
     /// * `Err(ArchivusError)` - Se houve erro ao acessar o diretório
     ///
     /// # Exemplos
-    /// ```rust
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), archivus::ArchivusError> {
     /// use archivus::Archivus;
     ///
     /// let utils = Archivus::new();
@@ -539,6 +1024,8 @@ impl Archivus {
     ///     
     ///     Ok(())
     /// }
+    /// # Ok(())
+    /// # }
     /// ```
     ///
     /// # Casos de Uso Comuns
@@ -551,150 +1038,217 @@ impl Archivus {
     /// Esta função NÃO é recursiva. Para listar subdiretórios de forma
     /// recursiva, use `list_with_filter()` com `recursive: true`.
     pub fn list_directories<P: AsRef<Path>>(&self, dir_path: P) -> Result<Vec<FileInfo>, ArchivusError> {
-        let mut directories = Vec::new();
-
-        for entry in fs::read_dir(dir_path)? {
-            let entry = entry?;
-            let file_info = FileInfo::new(entry.path())?;
-
-            if file_info.is_directory {
-                directories.push(file_info);
-            }
-        }
+        self.list_directories_with_filter(dir_path, &FileFilter::default())
+    }
 
+    /// Lista apenas os diretórios que correspondem a um filtro
+    ///
+    /// Arquivos e demais itens nunca são retornados. As outras opções do
+    /// filtro, como `include_hidden` e `recursive`, são respeitadas.
+    pub fn list_directories_with_filter<P: AsRef<Path>>(&self, dir_path: P, filter: &FileFilter) -> Result<Vec<FileInfo>, ArchivusError> {
+        let mut directories = self.list_with_filter(dir_path, &FileFilter {
+            include_files: false,
+            include_directories: true,
+            ..filter.clone()
+        })?;
+        directories.retain(|directory| directory.is_directory);
         Ok(directories)
     }
 
     /// Lista todos os itens (arquivos e diretórios) de um diretório
+    ///
+    /// Itens ocultos são incluídos; para excluí-los, use `list_all_with_filter`
+    /// com `include_hidden: false`.
     pub fn list_all<P: AsRef<Path>>(&self, dir_path: P) -> Result<Vec<FileInfo>, ArchivusError> {
-        let mut items = Vec::new();
-
-        for entry in fs::read_dir(dir_path)? {
-            let entry = entry?;
-            let file_info = FileInfo::new(entry.path())?;
-            items.push(file_info);
-        }
+        self.list_all_with_filter(dir_path, &FileFilter::default())
+    }
 
-        Ok(items)
+    /// Lista arquivos e diretórios que correspondem a um filtro
+    ///
+    /// Como `list_with_filter()`, mas sempre inclui os dois tipos, qualquer
+    /// que seja `include_files` e `include_directories` no filtro.
+    pub fn list_all_with_filter<P: AsRef<Path>>(&self, dir_path: P, filter: &FileFilter) -> Result<Vec<FileInfo>, ArchivusError> {
+        self.list_with_filter(dir_path, &FileFilter {
+            include_files: true,
+            include_directories: true,
+            ..filter.clone()
+        })
     }
 
     /// Lista arquivos com filtro personalizado
-    pub fn list_with_filter<P: AsRef<Path>>(&self, dir_path: P, filter: &FileFilter) -> Result<Vec<FileInfo>, ArchivusError> {
-        if filter.recursive {
-            self.list_with_filter_recursive(dir_path, filter)
-        } else {
-            self.list_with_filter_simple(dir_path, filter)
-        }
+    ///
+    /// Aceita um `&FileFilter` ou qualquer `Predicate` (veja `IntoFilter`).
+    /// Com `filter.threads` diferente de 1 o percurso é feito em paralelo;
+    /// use `filter.sorted` para obter sempre a mesma ordem.
+    pub fn list_with_filter<P: AsRef<Path>, F: IntoFilter>(&self, dir_path: P, filter: F) -> Result<Vec<FileInfo>, ArchivusError> {
+        Entries::new(dir_path, &filter.into_filter()).collect()
     }
 
-    // Implementação não-recursiva
-    fn list_with_filter_simple<P: AsRef<Path>>(&self, dir_path: P, filter: &FileFilter) -> Result<Vec<FileInfo>, ArchivusError> {
-        let mut filtered_items = Vec::new();
-
-        for entry in fs::read_dir(dir_path)? {
-            let entry = entry?;
-            let file_info = FileInfo::new(entry.path())?;
-
-            if self.matches_filter(&file_info, filter) {
-                filtered_items.push(file_info);
-            }
-        }
+    /// Lista arquivos com filtro personalizado, sem interromper em falhas
+    ///
+    /// Versão tolerante de `list_with_filter()`: subdiretórios ilegíveis ou
+    /// arquivos removidos durante a varredura são registrados em
+    /// `PartialResult::failures` e o percurso continua. Apenas a falha ao
+    /// abrir o próprio `dir_path` retorna `Err`.
+    ///
+    /// # Exemplos
+    /// ```rust,no_run
+    /// use archivus::{Archivus, FileFilter};
+    ///
+    /// let utils = Archivus::new();
+    /// let filter = FileFilter { recursive: true, ..Default::default() };
+    ///
+    /// let listing = utils.list_with_filter_partial("/mnt/compartilhado", &filter)?;
+    /// println!("{} itens encontrados", listing.value.len());
+    ///
+    /// for (path, error) in &listing.failures {
+    ///     eprintln!("Ignorado {}: {}", path.display(), error);
+    /// }
+    /// # Ok::<(), archivus::ArchivusError>(())
+    /// ```
+    pub fn list_with_filter_partial<P: AsRef<Path>>(&self, dir_path: P, filter: &FileFilter) -> Result<PartialResult<Vec<FileInfo>>, ArchivusError> {
+        let mut items = Vec::new();
+        let failures = Entries::new(dir_path, filter).for_each_partial(|item| items.push(item))?;
 
-        Ok(filtered_items)
+        Ok(PartialResult { value: items, failures })
     }
 
-    // Implementação recursiva
-    fn list_with_filter_recursive<P: AsRef<Path>>(&self, dir_path: P, filter: &FileFilter) -> Result<Vec<FileInfo>, ArchivusError> {
-        let mut filtered_items = Vec::new();
-
-        for entry in fs::read_dir(dir_path)? {
-            let entry = entry?;
-            let file_info = FileInfo::new(entry.path())?;
+    /// Percorre um diretório de forma preguiçosa, aplicando o filtro
+    ///
+    /// Diferente de `list_with_filter()`, nada é acumulado em memória: cada
+    /// entrada é lida apenas quando o iterador é consumido. Veja `Walker`.
+    pub fn walk<P: AsRef<Path>>(&self, dir_path: P, filter: &FileFilter) -> Walker {
+        Walker::new(dir_path, filter)
+    }
 
-            if self.matches_filter(&file_info, filter) {
-                filtered_items.push(file_info.clone());
-            }
+    /// Percorre um diretório com várias threads, aplicando o filtro
+    ///
+    /// O número de threads vem de `filter.threads`. Veja `ParallelWalker`.
+    pub fn walk_parallel<P: AsRef<Path>>(&self, dir_path: P, filter: &FileFilter) -> ParallelWalker {
+        ParallelWalker::new(dir_path, filter)
+    }
 
-            // Recursão em diretórios
-            if file_info.is_directory {
-                let mut sub_items = self.list_with_filter_recursive(&file_info.path, filter)?;
-                filtered_items.append(&mut sub_items);
-            }
-        }
+    // ================================================================
+    // BUSCA DE ARQUIVOS
+    // ================================================================
 
-        Ok(filtered_items)
+    /// Busca arquivos por nome (com wildcards simples)
+    ///
+    /// O padrão aceita `*` e `?` e ignora maiúsculas e minúsculas. Veja `Pattern`.
+    pub fn find_by_name<P: AsRef<Path>>(&self, dir_path: P, pattern: &str, recursive: bool) -> Result<Vec<FileInfo>, ArchivusError> {
+        self.find_by_name_with_filter(dir_path, pattern, &FileFilter {
+            recursive,
+            ..Default::default()
+        })
     }
 
-    // Verifica se um FileInfo corresponde ao filtro
-    fn matches_filter(&self, file_info: &FileInfo, filter: &FileFilter) -> bool {
-        // Verifica tipo (arquivo/diretório)
-        if file_info.is_file && !filter.include_files {
-            return false;
-        }
-        if file_info.is_directory && !filter.include_directories {
-            return false;
-        }
-
-        // Verifica extensão
-        if let Some(ref allowed_extensions) = filter.extensions {
-            if file_info.is_file {
-                match &file_info.extension {
-                    Some(ext) => {
-                        if !allowed_extensions.iter().any(|allowed| allowed.to_lowercase() == ext.to_lowercase()) {
-                            return false;
-                        }
-                    }
-                    None => return false,
-                }
-            }
-        }
+    /// Busca arquivos por nome aplicando também um filtro
+    ///
+    /// Permite, por exemplo, limitar a profundidade da busca com
+    /// `max_depth` ou restringir o resultado a arquivos com `include_directories`.
+    pub fn find_by_name_with_filter<P: AsRef<Path>>(&self, dir_path: P, pattern: &str, filter: &FileFilter) -> Result<Vec<FileInfo>, ArchivusError> {
+        let pattern = Pattern::new_ignore_case(pattern);
+        Entries::new(dir_path, filter)
+            .filter(|item| item.as_ref().map_or(true, |item| pattern.matches(&item.name)))
+            .collect()
+    }
 
-        // Verifica tamanho mínimo
-        if let Some(min_size) = filter.min_size {
-            if file_info.size < min_size {
-                return false;
-            }
-        }
+    /// Busca arquivos cujo nome corresponde a uma expressão regular
+    ///
+    /// A expressão pode corresponder a qualquer parte do nome; use `^` e `$`
+    /// para exigir o nome inteiro. Veja `Regex` para a sintaxe.
+    ///
+    /// # Exemplos
+    /// ```rust,no_run
+    /// # use archivus::Archivus;
+    /// # fn main() -> Result<(), archivus::ArchivusError> {
+    /// let utils = Archivus::new();
+    /// let reports = utils.find_by_regex("./dados", r"^report_\d{4}-\d{2}\.csv$", true)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn find_by_regex<P: AsRef<Path>>(&self, dir_path: P, pattern: &str, recursive: bool) -> Result<Vec<FileInfo>, ArchivusError> {
+        self.find_by_regex_with_filter(dir_path, pattern, &FileFilter {
+            recursive,
+            ..Default::default()
+        })
+    }
 
-        // Verifica tamanho máximo
-        if let Some(max_size) = filter.max_size {
-            if file_info.size > max_size {
-                return false;
-            }
-        }
+    /// Busca arquivos cujo nome corresponde a uma expressão regular, aplicando
+    /// também um filtro
+    pub fn find_by_regex_with_filter<P: AsRef<Path>>(&self, dir_path: P, pattern: &str, filter: &FileFilter) -> Result<Vec<FileInfo>, ArchivusError> {
+        let filter = FileFilter {
+            name_regex: Some(Regex::new(pattern)?),
+            ..filter.clone()
+        };
+        self.list_with_filter(dir_path, &filter)
+    }
 
-        true
+    /// Busca arquivos e diretórios a partir do diretório atual com um padrão glob
+    ///
+    /// O padrão é comparado com o caminho relativo ao diretório atual e aceita
+    /// `**`, classes `[a-z]`, alternativas `{a,b}` e escapes com `\\` (veja
+    /// `Glob`). Os resultados são ordenados por caminho.
+    ///
+    /// # Exemplos
+    /// ```rust,no_run
+    /// use archivus::Archivus;
+    ///
+    /// let utils = Archivus::new();
+    ///
+    /// for arquivo in utils.glob("src/**/*.{rs,toml}")? {
+    ///     println!("{}", arquivo.path.display());
+    /// }
+    /// # Ok::<(), archivus::ArchivusError>(())
+    /// ```
+    pub fn glob(&self, pattern: &str) -> Result<Vec<FileInfo>, ArchivusError> {
+        self.glob_with_filter(".", pattern, &FileFilter {
+            sorted: true,
+            ..Default::default()
+        })
     }
 
-    // ================================================================
-    // BUSCA DE ARQUIVOS
-    // ================================================================
+    /// Busca com um padrão glob relativo a `root`, aplicando também um filtro
+    ///
+    /// A busca é sempre recursiva, mas diretórios que não podem conter nenhum
+    /// caminho correspondente ao padrão não são abertos.
+    pub fn glob_with_filter<P: AsRef<Path>>(&self, root: P, pattern: &str, filter: &FileFilter) -> Result<Vec<FileInfo>, ArchivusError> {
+        let root = root.as_ref().to_path_buf();
+        let glob = Arc::new(Glob::new(pattern)?);
 
-    /// Busca arquivos por nome (com wildcards simples)
-    pub fn find_by_name<P: AsRef<Path>>(&self, dir_path: P, pattern: &str, recursive: bool) -> Result<Vec<FileInfo>, ArchivusError> {
-        let items = if recursive {
-            self.list_with_filter(dir_path, &FileFilter {
-                recursive: true,
-                ..Default::default()
-            })?
-        } else {
-            self.list_all(dir_path)?
+        let relative = {
+            let root = root.clone();
+            move |file_info: &FileInfo| file_info.path.strip_prefix(&root).map(Path::to_path_buf).unwrap_or_default()
+        };
+        let prune = {
+            let glob = Arc::clone(&glob);
+            let relative = relative.clone();
+            move |dir: &FileInfo| !glob::path_segments(&relative(dir)).is_some_and(|segments| glob.could_match_within(&segments))
         };
 
-        let filtered: Vec<FileInfo> = items.into_iter()
-            .filter(|item| self.matches_pattern(&item.name, pattern))
-            .collect();
+        let filter = FileFilter {
+            recursive: true,
+            ..filter.clone()
+        };
 
-        Ok(filtered)
+        Walker::new(&root, &filter)
+            .prune_with(Arc::new(prune))
+            .filter(|item| item.as_ref().map_or(true, |item| glob.matches(relative(item))))
+            .collect()
     }
 
     /// Busca arquivos por extensão
+    ///
+    /// A busca recursiva usa uma thread por núcleo e retorna os arquivos
+    /// ordenados por caminho.
     pub fn find_by_extension<P: AsRef<Path>>(&self, dir_path: P, extension: &str, recursive: bool) -> Result<Vec<FileInfo>, ArchivusError> {
         let filter = FileFilter {
             extensions: Some(vec![extension.to_string()]),
             include_directories: false,
             include_files: true,
             recursive,
+            threads: if recursive { 0 } else { 1 },
+            sorted: recursive,
             ..Default::default()
         };
 
@@ -715,63 +1269,224 @@ impl Archivus {
         self.list_with_filter(dir_path, &filter)
     }
 
-    // Verifica se um nome corresponde a um padrão simples (* e ?)
-    fn matches_pattern(&self, name: &str, pattern: &str) -> bool {
-        // Implementação simples de wildcard
-        // * = qualquer sequência de caracteres
-        // ? = qualquer caractere único
+    /// Busca arquivos pela idade da última modificação
+    ///
+    /// `min_age` mantém apenas arquivos modificados há pelo menos esse tempo
+    /// (ex.: mais antigos que 30 dias) e `max_age` apenas os modificados há no
+    /// máximo esse tempo.
+    ///
+    /// # Exemplos
+    /// ```rust,no_run
+    /// # use archivus::Archivus;
+    /// # use std::time::Duration;
+    /// # fn main() -> Result<(), archivus::ArchivusError> {
+    /// let utils = Archivus::new();
+    /// let old_logs = utils.find_by_age("./logs", Some(Duration::from_secs(30 * 24 * 60 * 60)), None, true)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn find_by_age<P: AsRef<Path>>(&self, dir_path: P, min_age: Option<Duration>, max_age: Option<Duration>, recursive: bool) -> Result<Vec<FileInfo>, ArchivusError> {
+        let filter = FileFilter {
+            modified_before: min_age.map(TimeSpec::Ago),
+            modified_after: max_age.map(TimeSpec::Ago),
+            include_directories: false,
+            include_files: true,
+            recursive,
+            ..Default::default()
+        };
 
-        if pattern == "*" {
-            return true;
-        }
-
-        // Se não tem wildcards, comparação direta
-        if !pattern.contains('*') && !pattern.contains('?') {
-            return name.to_lowercase() == pattern.to_lowercase();
-        }
+        self.list_with_filter(dir_path, &filter)
+    }
 
-        // Implementação básica de wildcards
-        self.wildcard_match(name, pattern)
+    /// Busca itens com permissões inseguras, com os motivos de cada um
+    ///
+    /// São reportados arquivos graváveis por qualquer usuário, arquivos com
+    /// setuid ou setgid e diretórios graváveis por qualquer usuário sem o
+    /// sticky bit. Links simbólicos não seguidos são ignorados. Em sistemas
+    /// que não são Unix o resultado é sempre vazio.
+    ///
+    /// # Exemplos
+    /// ```rust,no_run
+    /// # use archivus::Archivus;
+    /// # fn main() -> Result<(), archivus::ArchivusError> {
+    /// let utils = Archivus::new();
+    /// for insecure in utils.find_insecure_permissions("/usr/local", true)? {
+    ///     let reasons: Vec<String> = insecure.issues.iter().map(|issue| issue.to_string()).collect();
+    ///     println!("{}: {}", insecure.file.path.display(), reasons.join(", "));
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn find_insecure_permissions<P: AsRef<Path>>(&self, dir_path: P, recursive: bool) -> Result<Vec<InsecureFile>, ArchivusError> {
+        self.find_insecure_permissions_with_filter(dir_path, &FileFilter {
+            recursive,
+            ..Default::default()
+        })
     }
 
-    fn wildcard_match(&self, text: &str, pattern: &str) -> bool {
-        let text = text.to_lowercase();
-        let pattern = pattern.to_lowercase();
+    /// Busca itens com permissões inseguras entre os que correspondem a um filtro
+    ///
+    /// Permite, por exemplo, auditar apenas o sistema de arquivos da raiz com
+    /// `same_filesystem` ou ignorar diretórios com `prune_directories`.
+    pub fn find_insecure_permissions_with_filter<P: AsRef<Path>>(&self, dir_path: P, filter: &FileFilter) -> Result<Vec<InsecureFile>, ArchivusError> {
+        let mut insecure = Vec::new();
+        for file in Entries::new(dir_path, filter) {
+            let file = file?;
+            let issues = PermissionIssue::detect(&file);
+            if !issues.is_empty() {
+                insecure.push(InsecureFile { file, issues });
+            }
+        }
+        Ok(insecure)
+    }
 
-        self.wildcard_match_recursive(&text, &pattern, 0, 0)
+    /// Busca arquivos cujo conteúdo não corresponde à extensão
+    ///
+    /// Apenas arquivos com extensão conhecida (veja `mime_for_extension`)
+    /// são avaliados; arquivos vazios, ilegíveis ou que não são arquivos
+    /// comuns (pipes, sockets) são ignorados. Um `foto.jpg` que na
+    /// verdade é um PNG é reportado com `detected: "image/png"` e
+    /// `expected: "image/jpeg"`. Texto só é reconhecido em UTF-8 ou UTF-16
+    /// com BOM.
+    ///
+    /// # Exemplos
+    /// ```rust,no_run
+    /// # use archivus::Archivus;
+    /// # fn main() -> Result<(), archivus::ArchivusError> {
+    /// let utils = Archivus::new();
+    /// for mismatch in utils.find_extension_mismatches("./uploads", true)? {
+    ///     println!("{}: esperado {}, encontrado {}", mismatch.file.path.display(), mismatch.expected, mismatch.detected);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn find_extension_mismatches<P: AsRef<Path>>(&self, dir_path: P, recursive: bool) -> Result<Vec<ExtensionMismatch>, ArchivusError> {
+        self.find_extension_mismatches_with_filter(dir_path, &FileFilter {
+            recursive,
+            ..Default::default()
+        })
     }
 
-    fn wildcard_match_recursive(&self, text: &str, pattern: &str, text_idx: usize, pattern_idx: usize) -> bool {
-        if pattern_idx == pattern.len() {
-            return text_idx == text.len();
+    /// Busca arquivos cujo conteúdo não corresponde à extensão entre os que
+    /// correspondem a um filtro
+    pub fn find_extension_mismatches_with_filter<P: AsRef<Path>>(&self, dir_path: P, filter: &FileFilter) -> Result<Vec<ExtensionMismatch>, ArchivusError> {
+        let filter = FileFilter {
+            include_files: true,
+            include_directories: false,
+            ..filter.clone()
+        };
+
+        let mut mismatches = Vec::new();
+        for file in Entries::new(dir_path, &filter) {
+            if let Some(mismatch) = ExtensionMismatch::check(file?) {
+                mismatches.push(mismatch);
+            }
         }
+        Ok(mismatches)
+    }
 
-        let pattern_chars: Vec<char> = pattern.chars().collect();
-        let text_chars: Vec<char> = text.chars().collect();
+    /// Busca um padrão no conteúdo dos arquivos, linha a linha, como o `grep`
+    ///
+    /// Os arquivos pesquisados são os produzidos por `options.filter` (apenas
+    /// arquivos, na ordem do percurso). Cada linha com o padrão gera um
+    /// `ContentMatch` com o arquivo, o número da linha, a posição em bytes da
+    /// correspondência, o texto da linha e as linhas de contexto pedidas.
+    /// Linhas que não são UTF-8 válido são convertidas com substituição, e
+    /// nesse caso o intervalo se refere ao texto convertido.
+    ///
+    /// Apenas arquivos regulares são abertos: pipes, sockets e links quebrados
+    /// que passem pelo filtro são ignorados.
+    ///
+    /// # Erros
+    /// Retorna `ArchivusError::InvalidPattern` se a expressão regular for
+    /// inválida, ou o primeiro erro de leitura de um arquivo ou diretório.
+    /// Use `search_content_partial()` para continuar apesar dessas falhas.
+    ///
+    /// # Exemplos
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), archivus::ArchivusError> {
+    /// use archivus::{Archivus, FileFilter, SearchOptions, SearchPattern};
+    ///
+    /// let utils = Archivus::new();
+    /// let options = SearchOptions {
+    ///     ignore_case: true,
+    ///     context_after: 1,
+    ///     max_matches: Some(100),
+    ///     filter: FileFilter::parse("recursive ext:rs prune:target")?,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let pattern = SearchPattern::Regex(r"todo|fixme".to_string());
+    /// for found in utils.search_content("./src", &pattern, &options)? {
+    ///     println!("{}:{}: {}", found.path.display(), found.line_number, found.line);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn search_content<P: AsRef<Path>>(&self, root: P, pattern: &SearchPattern, options: &SearchOptions) -> Result<Vec<ContentMatch>, ArchivusError> {
+        let mut searcher = search::Searcher::new(pattern, options)?;
+        let filter = FileFilter {
+            include_files: true,
+            include_directories: false,
+            ..options.filter.clone()
+        };
 
-        if pattern_chars[pattern_idx] == '*' {
-            // Tenta todos os possíveis matches para *
-            for i in text_idx..=text_chars.len() {
-                if self.wildcard_match_recursive(text, pattern, i, pattern_idx + 1) {
-                    return true;
-                }
-            }
-            false
-        } else if pattern_chars[pattern_idx] == '?' {
-            // ? corresponde a exatamente um caractere
-            if text_idx < text_chars.len() {
-                self.wildcard_match_recursive(text, pattern, text_idx + 1, pattern_idx + 1)
-            } else {
-                false
+        for file in Entries::new(root, &filter) {
+            if searcher.is_done() {
+                break;
             }
-        } else {
-            // Caractere literal
-            if text_idx < text_chars.len() && text_chars[text_idx] == pattern_chars[pattern_idx] {
-                self.wildcard_match_recursive(text, pattern, text_idx + 1, pattern_idx + 1)
-            } else {
-                false
+            let file = file?;
+            if file.is_file {
+                searcher.search_file(&file.path)?;
             }
         }
+        Ok(searcher.matches)
+    }
+
+    /// Busca um padrão no conteúdo dos arquivos, sem interromper em falhas
+    ///
+    /// Versão tolerante de `search_content()`: arquivos que não puderam ser
+    /// lidos e subdiretórios ilegíveis são registrados em
+    /// `PartialResult::failures` e a busca continua. As correspondências já
+    /// encontradas em um arquivo que falhou no meio da leitura são mantidas.
+    ///
+    /// # Erros
+    /// Retorna `ArchivusError::InvalidPattern` se a expressão regular for
+    /// inválida, ou o erro ao abrir o próprio `root`.
+    ///
+    /// # Exemplos
+    /// ```rust,no_run
+    /// use archivus::{Archivus, SearchOptions, SearchPattern};
+    ///
+    /// let utils = Archivus::new();
+    /// let pattern = SearchPattern::Literal("senha".to_string());
+    ///
+    /// let result = utils.search_content_partial("/etc", &pattern, &SearchOptions::default())?;
+    /// for (path, error) in &result.failures {
+    ///     eprintln!("{}: {}", path.display(), error);
+    /// }
+    /// # Ok::<(), archivus::ArchivusError>(())
+    /// ```
+    pub fn search_content_partial<P: AsRef<Path>>(&self, root: P, pattern: &SearchPattern, options: &SearchOptions) -> Result<PartialResult<Vec<ContentMatch>>, ArchivusError> {
+        let mut searcher = search::Searcher::new(pattern, options)?;
+        let filter = FileFilter {
+            include_files: true,
+            include_directories: false,
+            ..options.filter.clone()
+        };
+
+        let mut unreadable = Vec::new();
+        let mut failures = Entries::new(root, &filter).for_each_partial(|file| {
+            if file.is_file
+                && !searcher.is_done()
+                && let Err(e) = searcher.search_file(&file.path)
+            {
+                unreadable.push((file.path, e));
+            }
+        })?;
+        failures.extend(unreadable);
+
+        Ok(PartialResult { value: searcher.matches, failures })
     }
 
     // ================================================================
@@ -881,7 +1596,8 @@ impl Archivus {
     /// * `Err(ArchivusError)` - Se houve erro ao ler o arquivo
     ///
     /// # Exemplos
-    /// ```rust
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), archivus::ArchivusError> {
     /// use archivus::Archivus;
     ///
     /// let utils = Archivus::new();
@@ -939,6 +1655,8 @@ impl Archivus {
     ///     
     ///     Ok(())
     /// }
+    /// # Ok(())
+    /// # }
     /// ```
     ///
     /// # Casos de Uso Comuns
@@ -1015,74 +1733,524 @@ impl Archivus {
         Ok(fs::rename(from, to)?)
     }
 
+    /// Espelha um diretório em outro, em um sentido
+    ///
+    /// Copia os arquivos novos ou diferentes (por tamanho e data ou pelo
+    /// conteúdo, conforme `options.compare`), cria diretórios e links
+    /// simbólicos e, com `options.delete`, remove do destino o que não existe
+    /// na origem. Links simbólicos são recriados, nunca seguidos, qualquer
+    /// que seja `options.filter.symlinks`. Datas e permissões são preservadas
+    /// por padrão; um item que só difere nas permissões tem apenas elas
+    /// ajustadas. Cada arquivo
+    /// é copiado para um temporário e renomeado, de modo que uma falha nunca
+    /// deixa um arquivo pela metade.
+    ///
+    /// Com `options.dry_run`, nada é alterado e o relatório traz o plano.
+    ///
+    /// # Erros
+    /// Retorna `ArchivusError::InvalidPath` se um diretório estiver dentro do
+    /// outro.
+    ///
+    /// # Exemplos
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), archivus::ArchivusError> {
+    /// use archivus::{Archivus, SyncOptions};
+    ///
+    /// let utils = Archivus::new();
+    /// let options = SyncOptions { delete: true, dry_run: true, ..Default::default() };
+    /// let plan = utils.sync("site", "/var/www/site", &options)?;
+    /// for action in &plan.actions {
+    ///     println!("{}", action);
+    /// }
+    ///
+    /// let report = utils.sync("site", "/var/www/site", &SyncOptions { dry_run: false, ..options })?;
+    /// println!("{} arquivos, {}", report.files_copied, report.formatted_bytes_copied());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn sync<P: AsRef<Path>, Q: AsRef<Path>>(&self, source: P, destination: Q, options: &SyncOptions) -> Result<SyncReport, ArchivusError> {
+        let (source, destination) = (source.as_ref(), destination.as_ref());
+
+        // O destino (e vários níveis acima dele) pode ainda não existir
+        let (Some(from), Some(to)) = (sync::resolve(source), sync::resolve(destination)) else {
+            return Err(ArchivusError::InvalidPath(format!(
+                "não foi possível resolver {} ou {}",
+                source.display(),
+                destination.display()
+            )));
+        };
+        if from.starts_with(&to) || to.starts_with(&from) {
+            return Err(ArchivusError::InvalidPath(format!(
+                "origem {} e destino {} se sobrepõem",
+                source.display(),
+                destination.display()
+            )));
+        }
+
+        // Links são recriados como links, nunca seguidos: seguir um link
+        // copiaria para o espelho a árvore para onde ele aponta
+        let filter = FileFilter {
+            symlinks: SymlinkPolicy::NoFollow,
+            ..options.filter.clone()
+        };
+        let left = self.snapshot(source, &filter, None)?;
+        let right = if destination.exists() {
+            self.snapshot(destination, &filter, None)?
+        } else {
+            Snapshot::from_files(destination, Vec::new(), None)?
+        };
+        let permissions = if options.preserve_permissions { sync::permission_changes(&left, &right) } else { Vec::new() };
+        let diff_options = DiffOptions {
+            compare: options.compare,
+            detect_renames: false,
+            algorithm: options.algorithm,
+            filter,
+        };
+        let diff = diff::diff(left, true, right, true, &diff_options)?;
+        let actions = sync::plan(&diff, options.delete, permissions);
+        sync::execute(source, destination, actions, diff.unchanged, options)
+    }
+
+    // ================================================================
+    // HASHES DE CONTEÚDO
+    // ================================================================
+
+    /// Calcula o hash do conteúdo de um arquivo
+    ///
+    /// O arquivo é lido em blocos, sem ser carregado inteiro na memória.
+    ///
+    /// # Exemplos
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), archivus::ArchivusError> {
+    /// use archivus::{Archivus, HashAlgorithm};
+    ///
+    /// let utils = Archivus::new();
+    /// let digest = utils.hash_file("release.tar.gz", HashAlgorithm::Sha256)?;
+    /// println!("{}  release.tar.gz", digest.to_hex());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn hash_file<P: AsRef<Path>>(&self, path: P, algorithm: HashAlgorithm) -> Result<Digest, ArchivusError> {
+        hash::hash_file(path.as_ref(), algorithm)
+    }
+
+    /// Calcula o hash de cada arquivo que corresponde a um filtro
+    ///
+    /// Apenas arquivos regulares são considerados: pipes, sockets e links
+    /// quebrados que passem pelo filtro são ignorados. O resultado é ordenado
+    /// por caminho.
+    pub fn hash_files<P: AsRef<Path>>(&self, root: P, algorithm: HashAlgorithm, filter: &FileFilter) -> Result<Vec<FileHash>, ArchivusError> {
+        let filter = FileFilter {
+            include_files: true,
+            include_directories: false,
+            ..filter.clone()
+        };
+
+        let mut hashes = Vec::new();
+        for file in Entries::new(root, &filter) {
+            let file = file?;
+            if !file.is_file {
+                continue;
+            }
+            let digest = hash::hash_file(&file.path, algorithm)?;
+            hashes.push(FileHash { file, digest });
+        }
+        hashes.sort_by(|a, b| a.file.path.cmp(&b.file.path));
+        Ok(hashes)
+    }
+
+    /// Calcula um hash único para uma árvore de diretórios
+    ///
+    /// O resultado é o hash, com o mesmo algoritmo, de uma linha
+    /// `<hash em hexadecimal>  <caminho relativo>\n` por arquivo, no formato
+    /// do `sha256sum`, com os caminhos relativos à raiz separados por `/` e
+    /// ordenados byte a byte. Assim, o hash depende apenas dos nomes e do
+    /// conteúdo dos arquivos, não da ordem do percurso, de datas ou de
+    /// permissões; diretórios vazios não alteram o resultado.
+    ///
+    /// # Exemplos
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), archivus::ArchivusError> {
+    /// use archivus::{Archivus, FileFilter, HashAlgorithm};
+    ///
+    /// let utils = Archivus::new();
+    /// let filter = FileFilter::parse("recursive prune:.git")?;
+    /// let digest = utils.hash_tree("./dist", HashAlgorithm::Blake2b, &filter)?;
+    /// println!("{}", digest.to_base64());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn hash_tree<P: AsRef<Path>>(&self, root: P, algorithm: HashAlgorithm, filter: &FileFilter) -> Result<Digest, ArchivusError> {
+        let root = root.as_ref();
+        let mut lines: Vec<(String, Digest)> = self
+            .hash_files(root, algorithm, filter)?
+            .into_iter()
+            .map(|hash| (relative_path(root, &hash.file.path), hash.digest))
+            .collect();
+        lines.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut hasher = ContentHasher::new(algorithm);
+        for (path, digest) in lines {
+            hasher.update(format!("{}  {}\n", digest.to_hex(), path).as_bytes());
+        }
+        Ok(hasher.finalize())
+    }
+
+    /// Escreve um manifesto de checksums dos arquivos de uma árvore
+    ///
+    /// Cada arquivo que corresponde ao filtro gera uma linha com o hash e o
+    /// caminho relativo a `root` (separado por `/`), em ordem de caminho. O
+    /// formato GNU é o de `sha256sum` e o BSD o de `sha256sum --tag`, e
+    /// ambos podem ser verificados com `sha256sum -c` dentro de `root`.
+    /// Retorna o número de arquivos listados.
+    ///
+    /// Se o manifesto for gravado dentro de `root`, exclua-o com o filtro
+    /// (ex.: `exclude_names`) para que ele não liste a si mesmo.
+    ///
+    /// # Exemplos
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), archivus::ArchivusError> {
+    /// use archivus::{Archivus, FileFilter, HashAlgorithm, ManifestFormat};
+    /// use std::fs::File;
+    ///
+    /// let utils = Archivus::new();
+    /// let filter = FileFilter::parse("recursive !name:SHA256SUMS")?;
+    /// let out = File::create("dist/SHA256SUMS")?;
+    /// utils.write_manifest("dist", &filter, HashAlgorithm::Sha256, ManifestFormat::Gnu, out)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn write_manifest<P: AsRef<Path>, W: Write>(&self, root: P, filter: &FileFilter, algorithm: HashAlgorithm, format: ManifestFormat, mut out: W) -> Result<usize, ArchivusError> {
+        let root = root.as_ref();
+        let mut entries: Vec<ManifestEntry> = self
+            .hash_files(root, algorithm, filter)?
+            .into_iter()
+            .map(|hash| ManifestEntry {
+                path: relative_path(root, &hash.file.path),
+                digest: hash.digest,
+            })
+            .collect();
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+
+        for entry in &entries {
+            writeln!(out, "{}", entry.to_line(format))?;
+        }
+        out.flush()?;
+        Ok(entries.len())
+    }
+
+    /// Verifica um manifesto de checksums
+    ///
+    /// Os caminhos do manifesto são relativos ao diretório onde ele está. O
+    /// relatório separa os arquivos corretos, modificados e ausentes, e os
+    /// arquivos do diretório (recursivamente) que não estão no manifesto.
+    /// Aceita os formatos GNU e BSD, inclusive misturados.
+    ///
+    /// Como no `sha256sum -c`, um item listado que não pode ser lido, ou que
+    /// não é um arquivo regular, é registrado em `unreadable` e a verificação
+    /// continua.
+    ///
+    /// # Exemplos
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), archivus::ArchivusError> {
+    /// use archivus::Archivus;
+    ///
+    /// let utils = Archivus::new();
+    /// let report = utils.verify_manifest("dist/SHA256SUMS")?;
+    /// for path in &report.modified {
+    ///     println!("{}: FALHOU", path.display());
+    /// }
+    /// if !report.is_valid() {
+    ///     std::process::exit(1);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn verify_manifest<P: AsRef<Path>>(&self, manifest: P) -> Result<ManifestReport, ArchivusError> {
+        self.verify_manifest_with_filter(manifest, &FileFilter {
+            recursive: true,
+            ..Default::default()
+        })
+    }
+
+    /// Verifica um manifesto de checksums, usando o filtro para procurar
+    /// arquivos extras
+    ///
+    /// Use o mesmo filtro de `write_manifest` para que arquivos excluídos na
+    /// geração não sejam reportados como extras. O próprio manifesto nunca é
+    /// considerado extra.
+    pub fn verify_manifest_with_filter<P: AsRef<Path>>(&self, manifest: P, filter: &FileFilter) -> Result<ManifestReport, ArchivusError> {
+        let manifest = manifest.as_ref();
+        let entries = parse_manifest(&fs::read_to_string(manifest)?)?;
+        let base = manifest.parent().filter(|parent| !parent.as_os_str().is_empty()).unwrap_or(Path::new("."));
+
+        let mut report = ManifestReport::default();
+        for entry in &entries {
+            let path = base.join(&entry.path);
+            // Diretórios e pipes não são lidos: um pipe bloquearia a leitura
+            match fs::metadata(&path) {
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                    report.missing.push(path);
+                    continue;
+                }
+                Ok(metadata) if metadata.is_file() => {}
+                _ => {
+                    report.unreadable.push(path);
+                    continue;
+                }
+            }
+            match hash::hash_file(&path, entry.digest.algorithm()) {
+                Ok(digest) if digest == entry.digest => report.ok.push(path),
+                Ok(_) => report.modified.push(path),
+                Err(ArchivusError::NotFound(_)) => report.missing.push(path),
+                Err(_) => report.unreadable.push(path),
+            }
+        }
+
+        let listed: HashSet<&str> = entries.iter().map(|entry| entry.path.trim_start_matches("./")).collect();
+        let manifest_name = manifest.file_name();
+        let filter = FileFilter {
+            include_files: true,
+            include_directories: false,
+            sorted: true,
+            ..filter.clone()
+        };
+        for file in Entries::new(base, &filter) {
+            let file = file?;
+            if !file.is_file {
+                continue;
+            }
+            let relative = relative_path(base, &file.path);
+            let is_manifest = file.path.parent() == Some(base) && file.path.file_name() == manifest_name;
+            if !is_manifest && !listed.contains(relative.as_str()) {
+                report.extra.push(file.path);
+            }
+        }
+        Ok(report)
+    }
+
+    // ================================================================
+    // SNAPSHOTS E COMPARAÇÃO
+    // ================================================================
+
+    /// Registra o estado de um diretório em um `Snapshot`
+    ///
+    /// Os itens são os de `list_with_filter`, com caminhos relativos a
+    /// `root`. Com um algoritmo, o hash de cada arquivo comum também é
+    /// registrado. Um nome que não é UTF-8 válido gera
+    /// `ArchivusError::InvalidPath`.
+    pub fn snapshot<P: AsRef<Path>>(&self, root: P, filter: &FileFilter, algorithm: Option<HashAlgorithm>) -> Result<Snapshot, ArchivusError> {
+        let root = root.as_ref();
+        Snapshot::from_files(root, self.list_with_filter(root, filter)?, algorithm)
+    }
+
+    /// Compara dois diretórios ou snapshots
+    ///
+    /// Cada lado pode ser um caminho (lido na hora, com `options.filter`) ou
+    /// um `Snapshot` salvo antes. O relatório lista os itens adicionados,
+    /// removidos, modificados (por tamanho e data ou pelo conteúdo, conforme
+    /// `options.compare`) e com tipo alterado, e agrupa em renomeações os
+    /// arquivos removidos e adicionados com o mesmo conteúdo.
+    ///
+    /// Hashes são calculados apenas quando necessários; em snapshots, são
+    /// usados os hashes registrados.
+    ///
+    /// # Erros
+    /// Com `CompareMode::Content`, retorna `ArchivusError::InvalidPath` se um
+    /// snapshot não tiver o hash de um arquivo que precisa ser comparado.
+    ///
+    /// # Exemplos
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), archivus::ArchivusError> {
+    /// use archivus::{Archivus, DiffOptions, Snapshot};
+    ///
+    /// let utils = Archivus::new();
+    /// let antes = Snapshot::load("antes.snap")?;
+    /// let report = utils.diff(antes, "/srv/app", &DiffOptions::default())?;
+    /// print!("{}", report);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn diff<L: Into<DiffSource>, R: Into<DiffSource>>(&self, left: L, right: R, options: &DiffOptions) -> Result<DiffReport, ArchivusError> {
+        let side = |source: DiffSource| match source {
+            DiffSource::Directory(root) => Ok::<_, ArchivusError>((self.snapshot(root, &options.filter, None)?, true)),
+            DiffSource::Snapshot(snapshot) => Ok((snapshot, false)),
+        };
+        let (left, left_live) = side(left.into())?;
+        let (right, right_live) = side(right.into())?;
+        diff::diff(left, left_live, right, right_live, options)
+    }
+
     // ================================================================
     // UTILITÁRIOS CONVENIENTES
     // ================================================================
 
     /// Obtém o tamanho total de um diretório (recursivamente)
     pub fn directory_size<P: AsRef<Path>>(&self, path: P) -> Result<u64, ArchivusError> {
-        let files = self.list_with_filter(path, &FileFilter {
+        Entries::new(path, &FileFilter {
             include_files: true,
             include_directories: false,
             recursive: true,
             ..Default::default()
-        })?;
+        }).try_fold(0, |total, file| file.map(|f| total + f.size))
+    }
 
-        Ok(files.iter().map(|f| f.size).sum())
+    /// Obtém o tamanho total dos arquivos que correspondem a um filtro
+    ///
+    /// Permite, por exemplo, somar apenas o sistema de arquivos da raiz com
+    /// `same_filesystem`, sem entrar em `/proc` ou em compartilhamentos de rede.
+    ///
+    /// # Exemplos
+    /// ```rust,no_run
+    /// # use archivus::{Archivus, FileFilter};
+    /// # fn main() -> Result<(), archivus::ArchivusError> {
+    /// let utils = Archivus::new();
+    /// let filter = FileFilter {
+    ///     recursive: true,
+    ///     same_filesystem: true,
+    ///     ..Default::default()
+    /// };
+    /// let total = utils.directory_size_with_filter("/", &filter)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn directory_size_with_filter<P: AsRef<Path>>(&self, path: P, filter: &FileFilter) -> Result<u64, ArchivusError> {
+        Entries::new(path, &FileFilter {
+            include_files: true,
+            include_directories: false,
+            ..filter.clone()
+        }).try_fold(0, |total, file| file.map(|f| total + f.size))
     }
 
-    /// Conta quantos arquivos existem em um diretório
-    pub fn count_files<P: AsRef<Path>>(&self, path: P, recursive: bool) -> Result<usize, ArchivusError> {
-        let files = self.list_with_filter(path, &FileFilter {
+    /// Obtém o tamanho total de um diretório, sem interromper em falhas
+    ///
+    /// Itens que não puderam ser lidos ficam de fora do total e são
+    /// registrados em `PartialResult::failures`.
+    pub fn directory_size_partial<P: AsRef<Path>>(&self, path: P) -> Result<PartialResult<u64>, ArchivusError> {
+        let mut total = 0;
+        let failures = Entries::new(path, &FileFilter {
             include_files: true,
             include_directories: false,
+            recursive: true,
+            ..Default::default()
+        }).for_each_partial(|file| total += file.size)?;
+
+        Ok(PartialResult { value: total, failures })
+    }
+
+    /// Conta quantos arquivos existem em um diretório
+    pub fn count_files<P: AsRef<Path>>(&self, path: P, recursive: bool) -> Result<usize, ArchivusError> {
+        self.count_files_with_filter(path, &FileFilter {
             recursive,
             ..Default::default()
-        })?;
+        })
+    }
 
-        Ok(files.len())
+    /// Conta quantos arquivos correspondem a um filtro (diretórios nunca são contados)
+    pub fn count_files_with_filter<P: AsRef<Path>>(&self, path: P, filter: &FileFilter) -> Result<usize, ArchivusError> {
+        Entries::new(path, &FileFilter {
+            include_files: true,
+            include_directories: false,
+            ..filter.clone()
+        }).try_fold(0, |count, file| file.map(|_| count + 1))
     }
 
     /// Conta quantos diretórios existem em um diretório
     pub fn count_directories<P: AsRef<Path>>(&self, path: P, recursive: bool) -> Result<usize, ArchivusError> {
-        let directories = self.list_with_filter(path, &FileFilter {
+        Entries::new(path, &FileFilter {
             include_files: false,
             include_directories: true,
             recursive,
             ..Default::default()
-        })?;
-
-        Ok(directories.len())
+        }).try_fold(0, |count, dir| dir.map(|_| count + 1))
     }
 
     /// Obtém estatísticas de um diretório
+    ///
+    /// O diretório é percorrido com uma thread por núcleo.
     pub fn directory_stats<P: AsRef<Path>>(&self, path: P) -> Result<DirectoryStats, ArchivusError> {
-        let all_items = self.list_with_filter(path, &FileFilter {
+        self.directory_stats_with_filter(path, &FileFilter {
             recursive: true,
+            threads: 0,
             ..Default::default()
-        })?;
+        })
+    }
 
+    /// Obtém estatísticas apenas dos itens que correspondem a um filtro
+    pub fn directory_stats_with_filter<P: AsRef<Path>>(&self, path: P, filter: &FileFilter) -> Result<DirectoryStats, ArchivusError> {
         let mut stats = DirectoryStats::default();
 
-        for item in all_items {
-            if item.is_file {
-                stats.file_count += 1;
-                stats.total_size += item.size;
+        for item in Entries::new(path, filter) {
+            stats.add(item?);
+        }
 
-                if let Some(ext) = item.extension {
-                    *stats.extensions.entry(ext).or_insert(0) += 1;
-                }
+        Ok(stats)
+    }
 
-                if item.size > stats.largest_file_size {
-                    stats.largest_file_size = item.size;
-                    stats.largest_file_name = Some(item.name);
-                }
-            } else {
-                stats.directory_count += 1;
-            }
+    /// Obtém estatísticas dos itens que correspondem a um filtro, sem interromper em falhas
+    ///
+    /// Versão tolerante de `directory_stats_with_filter()`: as estatísticas
+    /// cobrem tudo o que pôde ser lido e as falhas são registradas em
+    /// `PartialResult::failures`.
+    pub fn directory_stats_partial<P: AsRef<Path>>(&self, path: P, filter: &FileFilter) -> Result<PartialResult<DirectoryStats>, ArchivusError> {
+        let mut stats = DirectoryStats::default();
+        let failures = Entries::new(path, filter).for_each_partial(|item| stats.add(item))?;
+
+        Ok(PartialResult { value: stats, failures })
+    }
+
+    /// Encontra arquivos com conteúdo idêntico em uma ou mais raízes
+    ///
+    /// Os candidatos são agrupados por tamanho, depois por um hash dos
+    /// primeiros 4 KB, depois por um hash do conteúdo completo e, por fim,
+    /// confirmados byte a byte, de modo que cada arquivo só é lido por
+    /// inteiro se houver outro com o mesmo tamanho e o mesmo início.
+    ///
+    /// O filtro é aplicado a cada raiz (apenas arquivos são considerados).
+    /// Arquivos vazios são ignorados, e um arquivo alcançado por vários
+    /// caminhos (links físicos ou raízes sobrepostas) conta uma única vez.
+    ///
+    /// Falhas em itens individuais não interrompem a busca: um arquivo que
+    /// não pode ser lido (ou que sumiu durante a busca) é retirado do seu
+    /// grupo e registrado em `PartialResult::failures`, assim como os erros
+    /// de percurso. Apenas uma raiz que não pode ser aberta gera erro.
+    ///
+    /// # Exemplos
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), archivus::ArchivusError> {
+    /// use archivus::{Archivus, FileFilter};
+    ///
+    /// let utils = Archivus::new();
+    /// let filter = FileFilter { recursive: true, ..Default::default() };
+    /// let result = utils.find_duplicates(&["/backup/2023", "/backup/2024"], &filter)?;
+    /// let report = &result.value;
+    ///
+    /// for group in &report.groups {
+    ///     println!("{} cópias de {} bytes ({} desperdiçados)", group.files.len(), group.size, group.formatted_wasted_space());
+    ///     for file in &group.files {
+    ///         println!("  {}", file.path.display());
+    ///     }
+    /// }
+    /// println!("Total desperdiçado: {}", report.formatted_wasted_space());
+    /// for (path, error) in &result.failures {
+    ///     eprintln!("{}: {}", path.display(), error);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn find_duplicates<P: AsRef<Path>>(&self, roots: &[P], filter: &FileFilter) -> Result<PartialResult<DuplicateReport>, ArchivusError> {
+        let filter = FileFilter {
+            include_files: true,
+            include_directories: false,
+            ..filter.clone()
+        };
+        let mut files = Vec::new();
+        let mut failures = Vec::new();
+        for root in roots {
+            failures.extend(Entries::new(root, &filter).for_each_partial(|file| files.push(file))?);
         }
 
-        Ok(stats)
+        Ok(duplicates::find_duplicates(files, failures))
     }
 
     /// Converte um vetor de FileInfo em um HashMap para acesso rápido
@@ -1098,7 +2266,7 @@ impl Archivus {
 
         for file in files {
             let ext = file.extension.clone().unwrap_or_else(|| "sem_extensao".to_string());
-            groups.entry(ext).or_insert_with(Vec::new).push(file);
+            groups.entry(ext).or_default().push(file);
         }
 
         groups
@@ -1127,6 +2295,25 @@ pub struct DirectoryStats {
 }
 
 impl DirectoryStats {
+    // Acumula um item nas estatísticas
+    fn add(&mut self, item: FileInfo) {
+        if item.is_file {
+            self.file_count += 1;
+            self.total_size += item.size;
+
+            if let Some(ext) = item.extension {
+                *self.extensions.entry(ext).or_insert(0) += 1;
+            }
+
+            if item.size > self.largest_file_size {
+                self.largest_file_size = item.size;
+                self.largest_file_name = Some(item.name);
+            }
+        } else {
+            self.directory_count += 1;
+        }
+    }
+
     /// Formata o tamanho total de forma legível
     pub fn formatted_size(&self) -> String {
         format_bytes(self.total_size)
@@ -1138,10 +2325,32 @@ impl DirectoryStats {
     }
 }
 
+/// Resultado de uma operação que continua apesar de falhas em itens individuais
+#[derive(Debug, Clone)]
+pub struct PartialResult<T> {
+    /// Resultado calculado com tudo o que pôde ser lido
+    pub value: T,
+    /// Caminhos que não puderam ser lidos, com o erro correspondente
+    pub failures: Vec<(PathBuf, ArchivusError)>,
+}
+
+impl<T> PartialResult<T> {
+    /// Verifica se a operação terminou sem nenhuma falha
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
 // ================================================================
 // FUNÇÕES UTILITÁRIAS
 // ================================================================
 
+// Caminho relativo à raiz, com `/` como separador em qualquer plataforma
+pub(crate) fn relative_path(root: &Path, path: &Path) -> String {
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    relative.components().map(|component| component.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/")
+}
+
 /// Formata bytes em formato legível (KB, MB, GB, etc.)
 pub fn format_bytes(bytes: u64) -> String {
     const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
@@ -1180,10 +2389,71 @@ impl Default for Archivus {
 // ================================================================
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
     use std::fs;
-    use std::path::Path;
+
+    // Cria um diretório temporário vazio e exclusivo para o teste
+    pub(crate) fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("archivus_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    // Cria um pipe nomeado com o `mkfifo` do sistema
+    #[cfg(unix)]
+    pub(crate) fn make_fifo(path: &Path) {
+        let status = std::process::Command::new("mkfifo").arg(path).status().unwrap();
+        assert!(status.success());
+    }
+
+    // Arquivo comum fictício, sem datas nem metadados Unix, para testar
+    // filtros sem tocar no disco
+    pub(crate) fn file_info(name: &str, size: u64) -> FileInfo {
+        FileInfo {
+            path: PathBuf::from(name),
+            name: name.to_string(),
+            extension: name.rsplit_once('.').map(|(_, ext)| ext.to_string()),
+            size,
+            is_directory: false,
+            is_file: true,
+            is_symlink: false,
+            file_type: FileType::Regular,
+            is_hidden: false,
+            link_target: None,
+            device: None,
+            modified: None,
+            accessed: None,
+            created: None,
+            unix: None,
+            depth: 1,
+        }
+    }
+
+    // Nomes dos itens, em ordem alfabética
+    pub(crate) fn sorted_names(items: Vec<FileInfo>) -> Vec<String> {
+        let mut names: Vec<String> = items.into_iter().map(|f| f.name).collect();
+        names.sort();
+        names
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_list_special_files() {
+        let utils = Archivus::new();
+        let root = scratch_dir("list_special_files");
+        fs::create_dir_all(root.join("sub")).unwrap();
+        fs::write(root.join("a.txt"), "a").unwrap();
+        make_fifo(&root.join("pipe"));
+        std::os::unix::fs::symlink("inexistente", root.join("quebrado")).unwrap();
+
+        assert_eq!(sorted_names(utils.list_files(&root).unwrap()), ["a.txt"]);
+        assert_eq!(sorted_names(utils.list_directories(&root).unwrap()), ["sub"]);
+        assert_eq!(sorted_names(utils.list_all(&root).unwrap()), ["a.txt", "pipe", "quebrado", "sub"]);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
 
     #[test]
     fn test_file_exists() {
@@ -1211,11 +2481,419 @@ mod tests {
 
     #[test]
     fn test_wildcard_match() {
+        let matches = |name: &str, pattern: &str| Pattern::new_ignore_case(pattern).matches(name);
+
+        assert!(matches("test.txt", "*.txt"));
+        assert!(matches("arquivo.rs", "*.rs"));
+        assert!(matches("test", "t?st"));
+        assert!(!matches("test.txt", "*.rs"));
+    }
+
+    #[test]
+    fn test_glob_with_filter() {
+        let utils = Archivus::new();
+        let root = scratch_dir("glob_with_filter");
+        fs::create_dir_all(root.join("src/bin")).unwrap();
+        fs::create_dir_all(root.join("target")).unwrap();
+        for file in ["Cargo.toml", "src/lib.rs", "src/bin/cli.rs", "src/notas.md", "target/lib.rs"] {
+            fs::write(root.join(file), "x").unwrap();
+        }
+
+        let filter = FileFilter {
+            sorted: true,
+            ..Default::default()
+        };
+        let found: Vec<PathBuf> = utils.glob_with_filter(&root, "{src/**/*.rs,*.toml}", &filter).unwrap()
+            .into_iter()
+            .map(|f| f.path.strip_prefix(&root).unwrap().to_path_buf())
+            .collect();
+        assert_eq!(found, vec![
+            PathBuf::from("Cargo.toml"),
+            PathBuf::from("src/bin/cli.rs"),
+            PathBuf::from("src/lib.rs"),
+        ]);
+
+        assert!(matches!(utils.glob_with_filter(&root, "src/[a-", &filter), Err(ArchivusError::InvalidPattern(_))));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_regex_filters() {
+        let utils = Archivus::new();
+        let root = scratch_dir("regex_filters");
+        fs::create_dir_all(root.join("2024")).unwrap();
+        fs::create_dir_all(root.join("rascunhos")).unwrap();
+        for file in ["report_2024-01.csv", "report_2024-1.csv", "2024/report_2024-02.csv", "rascunhos/report_2024-03.csv"] {
+            fs::write(root.join(file), "x").unwrap();
+        }
+
+        let found = utils.find_by_regex(&root, r"^report_\d{4}-\d{2}\.csv$", true).unwrap();
+        assert_eq!(sorted_names(found), ["report_2024-01.csv", "report_2024-02.csv", "report_2024-03.csv"]);
+
+        let filter = FileFilter {
+            recursive: true,
+            include_directories: false,
+            exclude_path_regex: Some(Regex::new("rascunhos").unwrap()),
+            exclude_name_regex: Some(Regex::new(r"-\d\.").unwrap()),
+            ..Default::default()
+        };
+        assert_eq!(sorted_names(utils.list_with_filter(&root, &filter).unwrap()), ["report_2024-01.csv", "report_2024-02.csv"]);
+
+        assert!(matches!(utils.find_by_regex(&root, "(", true), Err(ArchivusError::InvalidPattern(_))));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_time_filters() {
+        let utils = Archivus::new();
+        let root = scratch_dir("time_filters");
+        fs::create_dir_all(&root).unwrap();
+
+        let now = SystemTime::now();
+        let day = Duration::from_secs(24 * 60 * 60);
+        for (name, age) in [("novo.log", 0), ("semana.log", 7), ("antigo.log", 40)] {
+            let file = fs::File::create(root.join(name)).unwrap();
+            file.set_modified(now - day * age).unwrap();
+        }
+
+        assert_eq!(sorted_names(utils.find_by_age(&root, Some(day * 30), None, false).unwrap()), ["antigo.log"]);
+        assert_eq!(sorted_names(utils.find_by_age(&root, None, Some(day), false).unwrap()), ["novo.log"]);
+        assert_eq!(sorted_names(utils.find_by_age(&root, Some(day), Some(day * 30), false).unwrap()), ["semana.log"]);
+
+        let filter = FileFilter {
+            modified_after: Some(TimeSpec::from(now - day * 10)),
+            modified_before: Some(TimeSpec::At(unix_seconds(Ok(now)).unwrap() - 60)),
+            ..Default::default()
+        };
+        assert_eq!(sorted_names(utils.list_with_filter(&root, &filter).unwrap()), ["semana.log"]);
+
+        // Intervalos enormes são limitados, sem estouro
+        assert_eq!(TimeSpec::days_ago(u64::MAX / 1000), TimeSpec::Ago(Duration::from_secs(u64::MAX)));
+        assert_eq!(TimeSpec::hours_ago(u64::MAX).resolve(1_000), 0);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_list_with_predicate() {
+        let utils = Archivus::new();
+        let root = scratch_dir("list_with_predicate");
+        fs::create_dir_all(root.join("sub")).unwrap();
+        fs::write(root.join("grande.log"), vec![b'x'; 2048]).unwrap();
+        fs::write(root.join("pequeno.log"), "x").unwrap();
+        fs::write(root.join("programa.core"), "x").unwrap();
+        fs::write(root.join("sub/outro.core"), "x").unwrap();
+
+        // Como no FileFilter, extensão não restringe diretórios
+        let predicate = Criterion::extensions(&["log"])
+            .and(Criterion::min_size(1024))
+            .or(Criterion::extensions(&["core"]))
+            .and(Criterion::files());
+
+        assert_eq!(sorted_names(utils.list_with_filter(&root, predicate.clone()).unwrap()), ["grande.log", "programa.core"]);
+
+        let filter = FileFilter {
+            recursive: true,
+            predicate: Some(Arc::new(predicate)),
+            ..Default::default()
+        };
+        assert_eq!(sorted_names(utils.list_with_filter(&root, &filter).unwrap()), ["grande.log", "outro.core", "programa.core"]);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_exclusions_and_pruning() {
+        let utils = Archivus::new();
+        let root = scratch_dir("exclusions");
+        fs::create_dir_all(root.join(".git/objects")).unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        for file in ["src/lib.rs", "src/lib.rs.bak", "src/notas.TMP", "src/lib.rs~", ".git/objects/abc", "README.md"] {
+            fs::write(root.join(file), "x").unwrap();
+        }
+
+        let filter = FileFilter {
+            recursive: true,
+            exclude_extensions: Some(vec!["bak".to_string(), "tmp".to_string()]),
+            exclude_names: Some(vec![Pattern::new("*~")]),
+            prune_directories: Some(vec![Pattern::new(".git")]),
+            ..Default::default()
+        };
+        assert_eq!(sorted_names(utils.list_with_filter(&root, &filter).unwrap()), ["README.md", "lib.rs", "src"]);
+
+        // Um diretório podado não é aberto, mesmo sem permissão de leitura
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(root.join(".git"), fs::Permissions::from_mode(0o000)).unwrap();
+            let listing = utils.list_with_filter_partial(&root, &filter).unwrap();
+            fs::set_permissions(root.join(".git"), fs::Permissions::from_mode(0o755)).unwrap();
+            assert!(listing.is_complete());
+        }
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_hidden_policy() {
+        let utils = Archivus::new();
+        let root = scratch_dir("hidden_policy");
+        fs::create_dir_all(root.join(".cache/sub")).unwrap();
+        fs::create_dir_all(root.join("docs")).unwrap();
+        for file in [".env", "notas.txt", ".cache/sub/dados.bin", "docs/.rascunho.txt", "docs/final.txt"] {
+            fs::write(root.join(file), "x").unwrap();
+        }
+
+        // Por padrão, itens ocultos continuam incluídos
+        assert_eq!(sorted_names(utils.list_files(&root).unwrap()), [".env", "notas.txt"]);
+        assert_eq!(sorted_names(utils.list_directories(&root).unwrap()), [".cache", "docs"]);
+        assert!(utils.list_all(&root).unwrap().iter().any(|f| f.name == ".env" && f.is_hidden));
+
+        let top_level = FileFilter {
+            include_hidden: false,
+            ..Default::default()
+        };
+        assert_eq!(sorted_names(utils.list_files_with_filter(&root, &top_level).unwrap()), ["notas.txt"]);
+        assert_eq!(sorted_names(utils.list_directories_with_filter(&root, &top_level).unwrap()), ["docs"]);
+        assert_eq!(sorted_names(utils.list_all_with_filter(&root, &top_level).unwrap()), ["docs", "notas.txt"]);
+
+        let visible = FileFilter {
+            recursive: true,
+            include_hidden: false,
+            ..Default::default()
+        };
+        assert_eq!(sorted_names(utils.list_files_with_filter(&root, &visible).unwrap()), ["final.txt", "notas.txt"]);
+        assert_eq!(sorted_names(utils.list_with_filter(&root, &visible).unwrap()), ["docs", "final.txt", "notas.txt"]);
+        assert_eq!(sorted_names(utils.find_by_name_with_filter(&root, "*.txt", &visible).unwrap()), ["final.txt", "notas.txt"]);
+
+        let stats = utils.directory_stats_with_filter(&root, &visible).unwrap();
+        assert_eq!((stats.file_count, stats.directory_count), (2, 1));
+        assert_eq!(utils.directory_stats(&root).unwrap().file_count, 5);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_same_filesystem() {
+        let utils = Archivus::new();
+        let root = scratch_dir("same_filesystem");
+        fs::create_dir_all(root.join("dados")).unwrap();
+        fs::write(root.join("dados/a.txt"), "abc").unwrap();
+        std::os::unix::fs::symlink("/proc/self", root.join("proc")).unwrap();
+
+        let root_device = FileInfo::new(&root).unwrap().device;
+        let proc_info = FileInfo::new(root.join("proc")).unwrap();
+        assert!(root_device.is_some());
+
+        if proc_info.device != root_device {
+            let filter = FileFilter {
+                recursive: true,
+                same_filesystem: true,
+                ..Default::default()
+            };
+            let items = utils.list_with_filter(&root, &filter).unwrap();
+            assert!(items.iter().any(|f| f.name == "proc"));
+            assert!(items.iter().all(|f| f.path.parent() != Some(&root.join("proc"))));
+            assert_eq!(utils.directory_size_with_filter(&root, &filter).unwrap(), 3);
+        }
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_search_content() {
+        let utils = Archivus::new();
+        let root = scratch_dir("search_content");
+        fs::create_dir_all(root.join("sub")).unwrap();
+        fs::write(root.join("a.txt"), "um\r\ndois TODO\ntrês\nquatro todo\ncinco\n").unwrap();
+        fs::write(root.join("sub/b.rs"), "fn main() {\n    // TODO: testar\n}").unwrap();
+        fs::write(root.join("binario.bin"), b"TODO\0\x01").unwrap();
+
+        let recursive = FileFilter {
+            recursive: true,
+            sorted: true,
+            ..Default::default()
+        };
+        let options = SearchOptions {
+            filter: recursive.clone(),
+            ..Default::default()
+        };
+        let literal = SearchPattern::Literal("TODO".to_string());
+        let found = utils.search_content(&root, &literal, &options).unwrap();
+        let summary: Vec<(String, usize, u64)> = found.iter().map(|m| (m.path.file_name().unwrap().to_string_lossy().to_string(), m.line_number, m.byte_offset)).collect();
+        assert_eq!(summary, [("a.txt".to_string(), 2, 9), ("b.rs".to_string(), 2, 19)]);
+        assert_eq!(found[0].line, "dois TODO");
+        assert_eq!(found[0].range, (5, 9));
+
+        // Maiúsculas ignoradas, contexto e limite por arquivo
+        let options = SearchOptions {
+            ignore_case: true,
+            context_before: 1,
+            context_after: 2,
+            max_matches_per_file: Some(1),
+            skip_binary: false,
+            filter: recursive.clone(),
+            ..Default::default()
+        };
+        let found = utils.search_content(&root, &literal, &options).unwrap();
+        assert_eq!(found.len(), 3);
+        assert_eq!(found[0].context_before, ["um"]);
+        assert_eq!(found[0].context_after, ["três", "quatro todo"]);
+        assert_eq!(found[1].path.file_name().unwrap(), "binario.bin");
+        assert_eq!(found[2].context_after, ["}"]);
+
+        let options = SearchOptions {
+            max_matches: Some(1),
+            filter: recursive,
+            ..Default::default()
+        };
+        let wildcard = SearchPattern::Wildcard("t?do*".to_string());
+        assert_eq!(utils.search_content(&root, &wildcard, &options).unwrap().len(), 1);
+        assert!(utils.search_content(&root, &SearchPattern::Regex("[".to_string()), &options).is_err());
+
+        // Um pipe na árvore não pode bloquear a busca; um arquivo ilegível
+        // interrompe `search_content`, mas não `search_content_partial`
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            make_fifo(&root.join("pipe.txt"));
+            let trancado = root.join("trancado.txt");
+            fs::write(&trancado, "TODO").unwrap();
+            fs::set_permissions(&trancado, fs::Permissions::from_mode(0o000)).unwrap();
+            // Com privilégios de superusuário a permissão não impede a leitura
+            let locked = fs::File::open(&trancado).is_err();
+
+            let options = SearchOptions {
+                filter: FileFilter { recursive: true, sorted: true, ..Default::default() },
+                ..Default::default()
+            };
+            let strict = utils.search_content(&root, &literal, &options);
+            assert_eq!(strict.is_err(), locked);
+
+            let result = utils.search_content_partial(&root, &literal, &options).unwrap();
+            let names: Vec<_> = result.value.iter().map(|m| m.path.file_name().unwrap().to_string_lossy().to_string()).collect();
+            assert_eq!(names, if locked { vec!["a.txt", "b.rs"] } else { vec!["a.txt", "b.rs", "trancado.txt"] });
+            let failed: Vec<_> = result.failures.iter().map(|(path, _)| path.clone()).collect();
+            assert_eq!(failed, if locked { vec![trancado.clone()] } else { vec![] });
+
+            fs::set_permissions(&trancado, fs::Permissions::from_mode(0o644)).unwrap();
+            fs::remove_file(root.join("pipe.txt")).unwrap();
+            fs::remove_file(&trancado).unwrap();
+        }
+
+        // A posição em bytes se refere ao arquivo, não à linha convertida
+        fs::write(root.join("latin1.txt"), b"ol\xe1 \xe9 TODO\n").unwrap();
+        let options = SearchOptions {
+            filter: FileFilter { names: Some(vec![Pattern::new("latin1.txt")]), ..Default::default() },
+            ..Default::default()
+        };
+        let found = utils.search_content(&root, &literal, &options).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].byte_offset, 6);
+        assert_eq!(found[0].line, "ol\u{FFFD} \u{FFFD} TODO");
+        assert_eq!(found[0].range, (10, 14));
+    }
+
+    #[test]
+    fn test_mime_detection() {
         let utils = Archivus::new();
+        let root = scratch_dir("mime_detection");
+        fs::create_dir_all(root.join("sub")).unwrap();
+        let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR".to_vec();
+        fs::write(root.join("foto.jpg"), &png).unwrap();
+        fs::write(root.join("imagem.png"), &png).unwrap();
+        fs::write(root.join("notas.txt"), "texto").unwrap();
+        fs::write(root.join("vazio.pdf"), "").unwrap();
+        fs::write(root.join("programa"), b"\x7fELF\x02\x01").unwrap();
+        fs::write(root.join("sub/pacote.docx"), b"PK\x03\x04").unwrap();
+        fs::write(root.join("sub/falso.zip"), "não sou zip").unwrap();
+        // Um pipe não pode ser lido sem bloquear: é ignorado
+        #[cfg(unix)]
+        make_fifo(&root.join("pipe.png"));
+
+        assert_eq!(utils.detect_mime_type(root.join("programa")).unwrap(), "application/x-executable");
+        assert!(utils.detect_mime_type(root.join("inexistente")).is_err());
+
+        let mut mismatches = utils.find_extension_mismatches(&root, true).unwrap();
+        mismatches.sort_by(|a, b| a.file.name.cmp(&b.file.name));
+        let found: Vec<(&str, &str, &str)> = mismatches.iter().map(|m| (m.file.name.as_str(), m.detected, m.expected)).collect();
+        assert_eq!(found, [
+            ("falso.zip", "text/plain", "application/zip"),
+            ("foto.jpg", "image/png", "image/jpeg"),
+        ]);
+        #[cfg(unix)]
+        fs::remove_file(root.join("pipe.png")).unwrap();
+
+        let images = FileFilter {
+            recursive: true,
+            mime_types: Some(vec![Pattern::new("image/*")]),
+            ..Default::default()
+        };
+        assert_eq!(sorted_names(utils.list_with_filter(&root, &images).unwrap()), ["foto.jpg", "imagem.png", "sub"]);
 
-        assert!(utils.matches_pattern("test.txt", "*.txt"));
-        assert!(utils.matches_pattern("arquivo.rs", "*.rs"));
-        assert!(utils.matches_pattern("test", "t?st"));
-        assert!(!utils.matches_pattern("test.txt", "*.rs"));
+        // A negação também examina apenas arquivos: o diretório continua
+        let not_png = FileFilter::parse("recursive !mime:image/png").unwrap();
+        assert_eq!(sorted_names(utils.list_with_filter(&root, &not_png).unwrap()), ["falso.zip", "notas.txt", "pacote.docx", "programa", "sub", "vazio.pdf"]);
+
+        fs::remove_dir_all(&root).unwrap();
     }
-}
\ No newline at end of file
+
+    #[cfg(unix)]
+    #[test]
+    fn test_permission_filters() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let utils = Archivus::new();
+        let root = scratch_dir("permission_filters");
+        fs::create_dir_all(root.join("publico")).unwrap();
+        fs::create_dir_all(root.join("tmp")).unwrap();
+        let modes = [
+            ("aberto.txt", 0o666),
+            ("programa", 0o4755),
+            ("grupo", 0o2755),
+            ("seguro.txt", 0o644),
+            ("publico", 0o777),
+            ("tmp", 0o1777),
+        ];
+        for (name, mode) in modes {
+            let path = root.join(name);
+            if !path.exists() {
+                fs::write(&path, "x").unwrap();
+            }
+            fs::set_permissions(&path, fs::Permissions::from_mode(mode)).unwrap();
+        }
+
+        let world_writable = FileFilter {
+            mode: Some(ModeFilter::Any(0o002)),
+            ..Default::default()
+        };
+        assert_eq!(sorted_names(utils.list_with_filter(&root, &world_writable).unwrap()), ["aberto.txt", "publico", "tmp"]);
+        assert_eq!(sorted_names(utils.list_with_filter(&root, Criterion::mode(ModeFilter::Exact(0o644))).unwrap()), ["seguro.txt"]);
+
+        let owner = FileInfo::new(&root).unwrap().unix.unwrap();
+        let owned = FileFilter {
+            uid: Some(owner.uid),
+            gid: Some(owner.gid),
+            ..Default::default()
+        };
+        assert_eq!(utils.list_with_filter(&root, &owned).unwrap().len(), 6);
+        assert!(utils.list_with_filter(&root, Criterion::uid(owner.uid.wrapping_add(1))).unwrap().is_empty());
+
+        let mut insecure = utils.find_insecure_permissions(&root, false).unwrap();
+        insecure.sort_by(|a, b| a.file.name.cmp(&b.file.name));
+        let found: Vec<(&str, &[PermissionIssue])> = insecure.iter().map(|i| (i.file.name.as_str(), i.issues.as_slice())).collect();
+        assert_eq!(found, [
+            ("aberto.txt", &[PermissionIssue::WorldWritable][..]),
+            ("grupo", &[PermissionIssue::Setgid][..]),
+            ("programa", &[PermissionIssue::Setuid][..]),
+            ("publico", &[PermissionIssue::WorldWritableDirectory][..]),
+        ]);
+        assert_eq!(PermissionIssue::Setuid.to_string(), "setuid");
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
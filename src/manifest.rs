@@ -0,0 +1,300 @@
+// ====================================================================
+// MANIFESTOS DE CHECKSUMS
+// ====================================================================
+// Leitura e escrita de listas de hashes nos formatos do GNU coreutils
+// (`sha256sum`) e BSD (`sha256sum --tag`)
+
+use std::path::PathBuf;
+
+use crate::{ArchivusError, Digest, HashAlgorithm};
+
+/// Formato das linhas de um manifesto
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ManifestFormat {
+    /// `<hash>  <caminho>`, como `sha256sum` e `b2sum`
+    #[default]
+    Gnu,
+    /// `SHA256 (<caminho>) = <hash>`, como `sha256sum --tag`
+    BsdTag,
+}
+
+/// Linha de um manifesto: caminho relativo (com `/`) e hash esperado
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestEntry {
+    pub path: String,
+    pub digest: Digest,
+}
+
+impl ManifestEntry {
+    /// Escreve a linha no formato pedido, sem a quebra de linha
+    ///
+    /// Caminhos com `\`, quebra de linha ou retorno de carro são escapados
+    /// como no GNU coreutils: a linha começa com `\` e esses caracteres
+    /// viram `\\`, `\n` e `\r`.
+    pub fn to_line(&self, format: ManifestFormat) -> String {
+        let escaped = self.path.contains(['\\', '\n', '\r']);
+        let path = if escaped {
+            self.path.replace('\\', "\\\\").replace('\n', "\\n").replace('\r', "\\r")
+        } else {
+            self.path.clone()
+        };
+        let prefix = if escaped { "\\" } else { "" };
+
+        match format {
+            ManifestFormat::Gnu => format!("{}{}  {}", prefix, self.digest.to_hex(), path),
+            ManifestFormat::BsdTag => format!("{}{} ({}) = {}", prefix, tag(self.digest.algorithm()), path, self.digest.to_hex()),
+        }
+    }
+}
+
+// Nome do algoritmo no formato BSD
+fn tag(algorithm: HashAlgorithm) -> &'static str {
+    match algorithm {
+        HashAlgorithm::Sha256 => "SHA256",
+        HashAlgorithm::Blake2b => "BLAKE2b",
+        HashAlgorithm::Crc32 => "CRC32",
+        HashAlgorithm::XxHash64 => "XXH64",
+    }
+}
+
+/// Lê um manifesto nos formatos GNU ou BSD, que podem ser misturados
+///
+/// No formato GNU o algoritmo é deduzido do tamanho do hash (64 dígitos
+/// para SHA-256, 128 para BLAKE2b, 8 para CRC32 e 16 para xxHash64). Linhas
+/// vazias são ignoradas e o marcador de modo binário (`*`) é aceito.
+///
+/// # Erros
+/// Retorna `ArchivusError::ParseError` com a posição (em caracteres, a
+/// partir de 0) da primeira linha inválida.
+///
+/// # Exemplos
+/// ```rust
+/// use archivus::{parse_manifest, HashAlgorithm};
+///
+/// let entries = parse_manifest(
+///     "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855  vazio.txt\n\
+///      CRC32 (dados/a.bin) = 352441c2\n",
+/// )?;
+/// assert_eq!(entries[0].path, "vazio.txt");
+/// assert_eq!(entries[1].digest.algorithm(), HashAlgorithm::Crc32);
+/// # Ok::<(), archivus::ArchivusError>(())
+/// ```
+pub fn parse_manifest(text: &str) -> Result<Vec<ManifestEntry>, ArchivusError> {
+    let mut entries = Vec::new();
+    let mut position = 0;
+    for (index, line) in text.split('\n').enumerate() {
+        let line_position = position;
+        position += line.chars().count() + 1;
+
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry = parse_line(line).map_err(|message| ArchivusError::ParseError {
+            position: line_position,
+            message: format!("linha {}: {}", index + 1, message),
+        })?;
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+fn parse_line(line: &str) -> Result<ManifestEntry, String> {
+    let (escaped, line) = match line.strip_prefix('\\') {
+        Some(rest) => (true, rest),
+        None => (false, line),
+    };
+
+    // GNU: `<hash>  <caminho>` ou `<hash> *<caminho>`
+    if let Some((hex, rest)) = line.split_once(' ')
+        && !hex.is_empty()
+        && hex.chars().all(|c| c.is_ascii_hexdigit())
+    {
+        let algorithm = HashAlgorithm::ALL
+            .into_iter()
+            .find(|algorithm| algorithm.digest_len() * 2 == hex.len())
+            .ok_or_else(|| format!("nenhum algoritmo tem hash de {} dígitos", hex.len()))?;
+        let path = rest
+            .strip_prefix(' ')
+            .or_else(|| rest.strip_prefix('*'))
+            .ok_or_else(|| "esperados dois espaços entre o hash e o caminho".to_string())?;
+        return entry(path, escaped, algorithm, hex);
+    }
+
+    // BSD: `<ALGORITMO> (<caminho>) = <hash>`
+    let (name, rest) = line.split_once(" (").ok_or_else(|| "linha não está no formato GNU nem BSD".to_string())?;
+    let (path, hex) = rest.rsplit_once(") = ").ok_or_else(|| "esperado ') = ' antes do hash".to_string())?;
+    let algorithm: HashAlgorithm = name.parse().map_err(|_| format!("algoritmo desconhecido '{}'", name))?;
+    entry(path, escaped, algorithm, hex)
+}
+
+fn entry(path: &str, escaped: bool, algorithm: HashAlgorithm, hex: &str) -> Result<ManifestEntry, String> {
+    if path.is_empty() {
+        return Err("caminho vazio".to_string());
+    }
+    let digest = Digest::from_hex(algorithm, hex).map_err(|e| match e {
+        ArchivusError::ParseError { message, .. } => message,
+        other => other.to_string(),
+    })?;
+    let path = if escaped { unescape(path)? } else { path.to_string() };
+    Ok(ManifestEntry { path, digest })
+}
+
+fn unescape(path: &str) -> Result<String, String> {
+    let mut unescaped = String::with_capacity(path.len());
+    let mut chars = path.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('\\') => unescaped.push('\\'),
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            other => return Err(format!("escape inválido no caminho: \\{}", other.map(String::from).unwrap_or_default())),
+        }
+    }
+    Ok(unescaped)
+}
+
+/// Resultado de `Archivus::verify_manifest`
+///
+/// Os caminhos são os do manifesto, unidos ao diretório do manifesto.
+#[derive(Debug, Clone, Default)]
+pub struct ManifestReport {
+    /// Arquivos com o hash esperado
+    pub ok: Vec<PathBuf>,
+    /// Arquivos com hash diferente do esperado
+    pub modified: Vec<PathBuf>,
+    /// Arquivos listados que não existem
+    pub missing: Vec<PathBuf>,
+    /// Itens listados que não puderam ser lidos ou não são arquivos regulares
+    pub unreadable: Vec<PathBuf>,
+    /// Arquivos presentes no diretório que não estão no manifesto
+    pub extra: Vec<PathBuf>,
+}
+
+impl ManifestReport {
+    /// Todos os arquivos listados existem, podem ser lidos e têm o hash
+    /// esperado
+    ///
+    /// Arquivos extras não invalidam o manifesto, como em `sha256sum -c`.
+    pub fn is_valid(&self) -> bool {
+        self.modified.is_empty() && self.missing.is_empty() && self.unreadable.is_empty()
+    }
+}
+
+// ====================================================================
+// TESTES UNITÁRIOS
+// ====================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(unix)]
+    use crate::tests::make_fifo;
+    use crate::tests::scratch_dir;
+    use crate::{Archivus, ContentHasher, FileFilter};
+    use std::fs;
+
+    fn parse_error(text: &str) -> (usize, String) {
+        match parse_manifest(text) {
+            Err(ArchivusError::ParseError { position, message }) => (position, message),
+            other => panic!("{:?} deveria falhar, mas retornou {:?}", text, other),
+        }
+    }
+
+    #[test]
+    fn test_manifest_lines() {
+        let entry = ManifestEntry {
+            path: "dir/a b.txt".to_string(),
+            digest: ContentHasher::digest(HashAlgorithm::Sha256, b"abc"),
+        };
+        let gnu = entry.to_line(ManifestFormat::Gnu);
+        let bsd = entry.to_line(ManifestFormat::BsdTag);
+        assert_eq!(gnu, "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  dir/a b.txt");
+        assert_eq!(bsd, "SHA256 (dir/a b.txt) = ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        assert_eq!(parse_manifest(&format!("{}\r\n\n{}\n", gnu, bsd)).unwrap(), [entry.clone(), entry]);
+
+        // Caminhos escapados e modo binário
+        let odd = ManifestEntry {
+            path: "linha\nnova\\barra (x) = y".to_string(),
+            digest: ContentHasher::digest(HashAlgorithm::XxHash64, b""),
+        };
+        assert_eq!(odd.to_line(ManifestFormat::Gnu), "\\ef46db3751d8e999  linha\\nnova\\\\barra (x) = y");
+        for format in [ManifestFormat::Gnu, ManifestFormat::BsdTag] {
+            assert_eq!(parse_manifest(&odd.to_line(format)).unwrap(), std::slice::from_ref(&odd));
+        }
+        let binary = parse_manifest("cbf43926 *programa.exe").unwrap();
+        assert_eq!((binary[0].path.as_str(), binary[0].digest.algorithm()), ("programa.exe", HashAlgorithm::Crc32));
+        assert!(parse_manifest("BLAKE2b (x) = ").is_err());
+
+        assert_eq!(parse_error("cbf43926  a\nabc  b").0, 12);
+        assert!(parse_error("cbf43926  a\nabc  b").1.starts_with("linha 2"));
+        assert_eq!(parse_error("MD5 (a) = d41d8cd98f00b204e9800998ecf8427e").0, 0);
+        assert_eq!(parse_error("cbf43926 a").0, 0);
+        assert_eq!(parse_error("SHA256 (a) = cbf43926").0, 0);
+    }
+
+    #[test]
+    fn test_manifest_round_trip() {
+        let utils = Archivus::new();
+        let root = scratch_dir("manifest_round_trip");
+        fs::create_dir_all(root.join("sub")).unwrap();
+        fs::write(root.join("a.txt"), "abc").unwrap();
+        fs::write(root.join("sub/b.bin"), "").unwrap();
+        fs::write(root.join("ignorado.tmp"), "x").unwrap();
+
+        let filter = FileFilter::parse("recursive !ext:tmp !name:SUMS*").unwrap();
+        let mut out = Vec::new();
+        let count = utils.write_manifest(&root, &filter, HashAlgorithm::Sha256, ManifestFormat::Gnu, &mut out).unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(String::from_utf8(out.clone()).unwrap(), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  a.txt\n\
+                                                             e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855  sub/b.bin\n");
+        fs::write(root.join("SUMS"), &out).unwrap();
+
+        let bsd = fs::File::create(root.join("SUMS.bsd")).unwrap();
+        utils.write_manifest(&root, &filter, HashAlgorithm::Blake2b, ManifestFormat::BsdTag, bsd).unwrap();
+        assert!(fs::read_to_string(root.join("SUMS.bsd")).unwrap().starts_with("BLAKE2b (a.txt) = ba80a53f"));
+
+        let report = utils.verify_manifest_with_filter(root.join("SUMS"), &filter).unwrap();
+        assert!(report.is_valid());
+        assert_eq!(report.ok.len(), 2);
+        assert!(report.extra.is_empty());
+
+        // Arquivo modificado, removido e novo
+        fs::write(root.join("a.txt"), "abd").unwrap();
+        fs::remove_file(root.join("sub/b.bin")).unwrap();
+        fs::write(root.join("sub/novo.txt"), "novo").unwrap();
+        for manifest in ["SUMS", "SUMS.bsd"] {
+            let report = utils.verify_manifest_with_filter(root.join(manifest), &filter).unwrap();
+            assert!(!report.is_valid());
+            assert_eq!(report.modified, [root.join("a.txt")]);
+            assert_eq!(report.missing, [root.join("sub/b.bin")]);
+            assert_eq!(report.extra, [root.join("sub").join("novo.txt")]);
+        }
+
+        // Sem o filtro, os demais arquivos do diretório também são extras,
+        // exceto o próprio manifesto
+        let report = utils.verify_manifest(root.join("SUMS")).unwrap();
+        assert_eq!(report.extra.len(), 3);
+
+        // Itens listados que não são arquivos regulares não interrompem a
+        // verificação
+        fs::create_dir(root.join("sub/b.bin")).unwrap();
+        #[cfg(unix)]
+        {
+            fs::remove_file(root.join("a.txt")).unwrap();
+            make_fifo(&root.join("a.txt"));
+        }
+        let report = utils.verify_manifest_with_filter(root.join("SUMS"), &filter).unwrap();
+        assert!(!report.is_valid());
+        assert!(report.missing.is_empty());
+        assert_eq!(report.extra, [root.join("sub").join("novo.txt")]);
+        #[cfg(unix)]
+        assert_eq!(report.unreadable, [root.join("a.txt"), root.join("sub/b.bin")]);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
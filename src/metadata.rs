@@ -0,0 +1,333 @@
+// ====================================================================
+// METADADOS ESTENDIDOS
+// ====================================================================
+// Tipo do item e metadados específicos de sistemas Unix (permissões,
+// dono, inode, blocos e datas com precisão de nanossegundos)
+
+use std::fmt;
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::FileInfo;
+
+const SETUID: u32 = 0o4000;
+const SETGID: u32 = 0o2000;
+const STICKY: u32 = 0o1000;
+const WORLD_WRITABLE: u32 = 0o0002;
+
+/// Tipo de um item do sistema de arquivos
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FileType {
+    /// Arquivo comum
+    Regular,
+    /// Diretório
+    Directory,
+    /// Link simbólico (não seguido ou com destino inexistente)
+    Symlink,
+    /// Pipe nomeado (FIFO)
+    Fifo,
+    /// Socket Unix
+    Socket,
+    /// Dispositivo de bloco
+    BlockDevice,
+    /// Dispositivo de caractere
+    CharDevice,
+    /// Tipo não reconhecido na plataforma
+    Unknown,
+}
+
+impl FileType {
+    pub(crate) fn from_std(file_type: fs::FileType) -> Self {
+        if file_type.is_file() {
+            return FileType::Regular;
+        }
+        if file_type.is_dir() {
+            return FileType::Directory;
+        }
+        if file_type.is_symlink() {
+            return FileType::Symlink;
+        }
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::FileTypeExt;
+            if file_type.is_fifo() {
+                return FileType::Fifo;
+            }
+            if file_type.is_socket() {
+                return FileType::Socket;
+            }
+            if file_type.is_block_device() {
+                return FileType::BlockDevice;
+            }
+            if file_type.is_char_device() {
+                return FileType::CharDevice;
+            }
+        }
+
+        FileType::Unknown
+    }
+}
+
+/// Instante com precisão de nanossegundos, relativo à época Unix
+///
+/// `seconds` é negativo para instantes anteriores a 1970; `nanoseconds`
+/// está sempre entre 0 e 999.999.999.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp {
+    /// Segundos desde a época Unix
+    pub seconds: i64,
+    /// Fração do segundo, em nanossegundos
+    pub nanoseconds: u32,
+}
+
+impl Timestamp {
+    /// Cria um Timestamp a partir de segundos e nanossegundos, normalizando
+    /// nanossegundos fora do intervalo
+    pub fn new(seconds: i64, nanoseconds: i64) -> Self {
+        Self {
+            seconds: seconds + nanoseconds.div_euclid(1_000_000_000),
+            nanoseconds: nanoseconds.rem_euclid(1_000_000_000) as u32,
+        }
+    }
+}
+
+impl From<SystemTime> for Timestamp {
+    fn from(time: SystemTime) -> Self {
+        match time.duration_since(UNIX_EPOCH) {
+            Ok(after) => Timestamp::new(after.as_secs() as i64, after.subsec_nanos() as i64),
+            Err(before) => {
+                let before = before.duration();
+                Timestamp::new(-(before.as_secs() as i64), -(before.subsec_nanos() as i64))
+            }
+        }
+    }
+}
+
+/// Metadados de sistemas Unix, obtidos com `std::os::unix::fs::MetadataExt`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnixMetadata {
+    /// Modo completo (`st_mode`), com tipo e permissões
+    pub mode: u32,
+    /// Dono
+    pub uid: u32,
+    /// Grupo
+    pub gid: u32,
+    /// Número do inode
+    pub inode: u64,
+    /// Quantidade de links físicos
+    pub nlink: u64,
+    /// Dispositivo representado, para dispositivos de bloco e caractere
+    pub rdev: u64,
+    /// Blocos de 512 bytes alocados
+    pub blocks: u64,
+    /// Tamanho de bloco preferido para I/O
+    pub block_size: u64,
+    /// Último acesso (`atime`)
+    pub accessed: Timestamp,
+    /// Última modificação do conteúdo (`mtime`)
+    pub modified: Timestamp,
+    /// Última alteração dos metadados (`ctime`)
+    pub changed: Timestamp,
+    /// Criação, se o sistema de arquivos registrar
+    pub created: Option<Timestamp>,
+}
+
+impl UnixMetadata {
+    /// Bits de permissão, incluindo setuid, setgid e sticky (`mode & 0o7777`)
+    pub fn permissions(&self) -> u32 {
+        self.mode & 0o7777
+    }
+
+    /// Espaço ocupado em disco, em bytes (`blocks * 512`)
+    pub fn allocated_size(&self) -> u64 {
+        self.blocks * 512
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn from_metadata(metadata: &fs::Metadata) -> Option<Self> {
+        use std::os::unix::fs::MetadataExt;
+
+        Some(Self {
+            mode: metadata.mode(),
+            uid: metadata.uid(),
+            gid: metadata.gid(),
+            inode: metadata.ino(),
+            nlink: metadata.nlink(),
+            rdev: metadata.rdev(),
+            blocks: metadata.blocks(),
+            block_size: metadata.blksize(),
+            accessed: Timestamp::new(metadata.atime(), metadata.atime_nsec()),
+            modified: Timestamp::new(metadata.mtime(), metadata.mtime_nsec()),
+            changed: Timestamp::new(metadata.ctime(), metadata.ctime_nsec()),
+            created: metadata.created().ok().map(Timestamp::from),
+        })
+    }
+
+    #[cfg(not(unix))]
+    pub(crate) fn from_metadata(_metadata: &fs::Metadata) -> Option<Self> {
+        None
+    }
+}
+
+/// Critério sobre os bits de permissão (`mode & 0o7777`), como em `find -perm`
+///
+/// # Exemplos
+/// ```rust
+/// use archivus::{FileFilter, ModeFilter};
+///
+/// // Arquivos graváveis por qualquer usuário
+/// let filter = FileFilter {
+///     mode: Some(ModeFilter::Any(0o002)),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModeFilter {
+    /// Pelo menos um dos bits ligado (`find -perm /modo`)
+    Any(u32),
+    /// Todos os bits ligados (`find -perm -modo`)
+    All(u32),
+    /// Permissões exatamente iguais (`find -perm modo`)
+    Exact(u32),
+}
+
+impl ModeFilter {
+    /// Verifica se os bits de permissão atendem ao critério
+    pub fn matches(&self, permissions: u32) -> bool {
+        let permissions = permissions & 0o7777;
+        match *self {
+            ModeFilter::Any(bits) => permissions & bits != 0,
+            ModeFilter::All(bits) => permissions & bits == bits,
+            ModeFilter::Exact(bits) => permissions == bits,
+        }
+    }
+}
+
+/// Problema de permissão encontrado por `Archivus::find_insecure_permissions`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PermissionIssue {
+    /// Arquivo gravável por qualquer usuário
+    WorldWritable,
+    /// Diretório gravável por qualquer usuário sem o sticky bit, permitindo
+    /// que qualquer um apague arquivos de outros
+    WorldWritableDirectory,
+    /// Arquivo executado com os privilégios do dono (setuid)
+    Setuid,
+    /// Arquivo executado com os privilégios do grupo (setgid)
+    Setgid,
+}
+
+impl PermissionIssue {
+    // Problemas das permissões de um item; links simbólicos não têm
+    // permissões próprias e são ignorados
+    pub(crate) fn detect(file_info: &FileInfo) -> Vec<PermissionIssue> {
+        let Some(unix) = &file_info.unix else {
+            return Vec::new();
+        };
+        let permissions = unix.permissions();
+        let mut issues = Vec::new();
+
+        match file_info.file_type {
+            FileType::Directory => {
+                if permissions & WORLD_WRITABLE != 0 && permissions & STICKY == 0 {
+                    issues.push(PermissionIssue::WorldWritableDirectory);
+                }
+            }
+            FileType::Symlink => {}
+            _ => {
+                if permissions & WORLD_WRITABLE != 0 {
+                    issues.push(PermissionIssue::WorldWritable);
+                }
+                if permissions & SETUID != 0 {
+                    issues.push(PermissionIssue::Setuid);
+                }
+                if permissions & SETGID != 0 {
+                    issues.push(PermissionIssue::Setgid);
+                }
+            }
+        }
+        issues
+    }
+}
+
+impl fmt::Display for PermissionIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PermissionIssue::WorldWritable => write!(f, "gravável por qualquer usuário"),
+            PermissionIssue::WorldWritableDirectory => write!(f, "diretório gravável por qualquer usuário sem sticky bit"),
+            PermissionIssue::Setuid => write!(f, "setuid"),
+            PermissionIssue::Setgid => write!(f, "setgid"),
+        }
+    }
+}
+
+/// Item com permissões inseguras e os motivos encontrados
+#[derive(Debug, Clone)]
+pub struct InsecureFile {
+    /// Item examinado
+    pub file: FileInfo,
+    /// Problemas encontrados nas permissões
+    pub issues: Vec<PermissionIssue>,
+}
+
+// ====================================================================
+// TESTES UNITÁRIOS
+// ====================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_mode_filter() {
+        assert!(ModeFilter::Any(0o022).matches(0o644 | 0o002));
+        assert!(!ModeFilter::Any(0o022).matches(0o644));
+        assert!(ModeFilter::All(0o4100).matches(0o4755));
+        assert!(!ModeFilter::All(0o4100).matches(0o4644));
+        assert!(ModeFilter::Exact(0o644).matches(0o100644));
+        assert!(!ModeFilter::Exact(0o644).matches(0o664));
+    }
+
+    #[test]
+    fn test_timestamp() {
+        assert_eq!(Timestamp::new(1, 1_500_000_000), Timestamp { seconds: 2, nanoseconds: 500_000_000 });
+        assert_eq!(Timestamp::new(0, -1), Timestamp { seconds: -1, nanoseconds: 999_999_999 });
+
+        let time = UNIX_EPOCH + Duration::new(1_700_000_000, 123);
+        assert_eq!(Timestamp::from(time), Timestamp { seconds: 1_700_000_000, nanoseconds: 123 });
+        let before = UNIX_EPOCH - Duration::new(1, 250_000_000);
+        assert_eq!(Timestamp::from(before), Timestamp { seconds: -2, nanoseconds: 750_000_000 });
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_unix_metadata() {
+        use crate::tests::scratch_dir;
+        use crate::FileInfo;
+        use std::os::unix::fs::PermissionsExt;
+
+        let root = scratch_dir("unix_metadata");
+        fs::create_dir_all(&root).unwrap();
+        let file = root.join("dados.bin");
+        fs::write(&file, vec![0u8; 10_000]).unwrap();
+        fs::set_permissions(&file, fs::Permissions::from_mode(0o640)).unwrap();
+        fs::hard_link(&file, root.join("link.bin")).unwrap();
+        std::os::unix::fs::symlink("dados.bin", root.join("simbolico")).unwrap();
+
+        let info = FileInfo::new(&file).unwrap();
+        let unix = info.unix.as_ref().unwrap();
+        assert_eq!(info.file_type, FileType::Regular);
+        assert_eq!(unix.permissions(), 0o640);
+        assert_eq!(unix.nlink, 2);
+        assert!(unix.allocated_size() >= 8192 || unix.blocks == 0);
+        assert_eq!(Some(unix.modified.seconds as u64), info.modified);
+
+        assert_eq!(FileInfo::new_no_follow(root.join("simbolico")).unwrap().file_type, FileType::Symlink);
+        assert_eq!(FileInfo::new(&root).unwrap().file_type, FileType::Directory);
+        assert_eq!(FileInfo::new("/dev/null").unwrap().file_type, FileType::CharDevice);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,261 @@
+// ====================================================================
+// DETECÇÃO DE TIPO PELO CONTEÚDO
+// ====================================================================
+// Identifica o formato de um arquivo pelos primeiros bytes (números
+// mágicos), sem depender da extensão, e devolve o tipo MIME
+
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+use crate::FileInfo;
+
+/// Quantidade de bytes lidos do início do arquivo para a detecção
+pub(crate) const SNIFF_LEN: usize = 8192;
+
+/// Tipo de arquivos vazios
+pub const MIME_EMPTY: &str = "application/x-empty";
+/// Tipo de texto (UTF-8, ou UTF-16 com BOM)
+pub const MIME_TEXT: &str = "text/plain";
+/// Tipo de conteúdo binário não reconhecido
+pub const MIME_BINARY: &str = "application/octet-stream";
+
+// Número mágico: bytes esperados a partir de um deslocamento
+struct Magic {
+    offset: usize,
+    bytes: &'static [u8],
+}
+
+const fn at(offset: usize, bytes: &'static [u8]) -> Magic {
+    Magic { offset, bytes }
+}
+
+// Formato conhecido: tipo MIME, extensões usuais e assinaturas (todas as
+// assinaturas de uma linha precisam corresponder)
+struct Format {
+    mime: &'static str,
+    extensions: &'static [&'static str],
+    signatures: &'static [&'static [Magic]],
+}
+
+// Formatos conhecidos; os mais específicos vêm antes dos que compartilham
+// o mesmo prefixo (RIFF, ftyp)
+const FORMATS: &[Format] = &[
+    Format { mime: "image/png", extensions: &["png"], signatures: &[&[at(0, b"\x89PNG\r\n\x1a\n")]] },
+    Format { mime: "image/jpeg", extensions: &["jpg", "jpeg", "jpe", "jfif"], signatures: &[&[at(0, b"\xff\xd8\xff")]] },
+    Format { mime: "image/gif", extensions: &["gif"], signatures: &[&[at(0, b"GIF87a")], &[at(0, b"GIF89a")]] },
+    Format { mime: "image/webp", extensions: &["webp"], signatures: &[&[at(0, b"RIFF"), at(8, b"WEBP")]] },
+    Format { mime: "image/tiff", extensions: &["tif", "tiff"], signatures: &[&[at(0, b"II*\0")], &[at(0, b"MM\0*")]] },
+    Format { mime: "application/pdf", extensions: &["pdf"], signatures: &[&[at(0, b"%PDF-")]] },
+    Format {
+        mime: "application/zip",
+        extensions: &["zip", "jar", "war", "apk", "docx", "xlsx", "pptx", "odt", "ods", "odp", "epub", "whl"],
+        signatures: &[&[at(0, b"PK\x03\x04")], &[at(0, b"PK\x05\x06")], &[at(0, b"PK\x07\x08")]],
+    },
+    Format { mime: "application/gzip", extensions: &["gz", "tgz"], signatures: &[&[at(0, b"\x1f\x8b")]] },
+    Format { mime: "application/x-bzip2", extensions: &["bz2", "tbz2"], signatures: &[&[at(0, b"BZh")]] },
+    Format { mime: "application/x-xz", extensions: &["xz", "txz"], signatures: &[&[at(0, b"\xfd7zXZ\0")]] },
+    Format { mime: "application/zstd", extensions: &["zst"], signatures: &[&[at(0, b"\x28\xb5\x2f\xfd")]] },
+    Format { mime: "application/x-7z-compressed", extensions: &["7z"], signatures: &[&[at(0, b"7z\xbc\xaf\x27\x1c")]] },
+    Format { mime: "application/vnd.rar", extensions: &["rar"], signatures: &[&[at(0, b"Rar!\x1a\x07")]] },
+    Format { mime: "application/x-tar", extensions: &["tar"], signatures: &[&[at(257, b"ustar")]] },
+    Format { mime: "application/x-executable", extensions: &["elf", "so", "o"], signatures: &[&[at(0, b"\x7fELF")]] },
+    Format {
+        mime: "application/vnd.microsoft.portable-executable",
+        extensions: &["exe", "dll", "sys"],
+        signatures: &[&[at(0, b"MZ")]],
+    },
+    Format { mime: "application/wasm", extensions: &["wasm"], signatures: &[&[at(0, b"\0asm")]] },
+    Format {
+        mime: "application/vnd.sqlite3",
+        extensions: &["sqlite", "sqlite3", "db"],
+        signatures: &[&[at(0, b"SQLite format 3\0")]],
+    },
+    Format { mime: "audio/mpeg", extensions: &["mp3"], signatures: &[&[at(0, b"ID3")]] },
+    Format { mime: "audio/ogg", extensions: &["ogg", "oga", "opus"], signatures: &[&[at(0, b"OggS")]] },
+    Format { mime: "audio/flac", extensions: &["flac"], signatures: &[&[at(0, b"fLaC")]] },
+    Format { mime: "audio/wav", extensions: &["wav"], signatures: &[&[at(0, b"RIFF"), at(8, b"WAVE")]] },
+    Format { mime: "video/quicktime", extensions: &["mov"], signatures: &[&[at(4, b"ftypqt  ")]] },
+    Format { mime: "video/mp4", extensions: &["mp4", "m4v", "m4a"], signatures: &[&[at(4, b"ftyp")]] },
+    // Texto não tem assinatura: é reconhecido por `is_text`
+    Format { mime: MIME_TEXT, extensions: &["txt", "text"], signatures: &[] },
+];
+
+/// Detecta o tipo MIME a partir do início do conteúdo de um arquivo
+///
+/// Formatos com número mágico conhecido (PNG, JPEG, GIF, PDF, ZIP, gzip,
+/// ELF, tar, SQLite...) são reconhecidos primeiro. Conteúdo sem assinatura
+/// é `text/plain` se for UTF-8 válido sem caracteres de controle, ou UTF-16
+/// com BOM; caso contrário, `application/octet-stream`. Conteúdo vazio é
+/// `application/x-empty`.
+///
+/// Bastam os primeiros 8 KB do arquivo; um caractere UTF-8 cortado no fim
+/// do trecho não impede a detecção de texto.
+///
+/// # Exemplos
+/// ```rust
+/// use archivus::detect_mime;
+///
+/// assert_eq!(detect_mime(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"), "image/png");
+/// assert_eq!(detect_mime("olá, mundo\n".as_bytes()), "text/plain");
+/// assert_eq!(detect_mime(&[0, 1, 2, 3]), "application/octet-stream");
+/// ```
+pub fn detect_mime(data: &[u8]) -> &'static str {
+    if data.is_empty() {
+        return MIME_EMPTY;
+    }
+
+    let signature_matches = |signature: &[Magic]| {
+        signature.iter().all(|magic| data.get(magic.offset..magic.offset + magic.bytes.len()) == Some(magic.bytes))
+    };
+    if let Some(format) = FORMATS.iter().find(|format| format.signatures.iter().any(|signature| signature_matches(signature))) {
+        return format.mime;
+    }
+
+    if is_text(data) { MIME_TEXT } else { MIME_BINARY }
+}
+
+/// Extensões usuais de um tipo MIME (vazio para tipos desconhecidos)
+///
+/// # Exemplos
+/// ```rust
+/// use archivus::extensions_for_mime;
+///
+/// assert_eq!(extensions_for_mime("image/jpeg"), ["jpg", "jpeg", "jpe", "jfif"]);
+/// assert!(extensions_for_mime("application/octet-stream").is_empty());
+/// ```
+pub fn extensions_for_mime(mime: &str) -> &'static [&'static str] {
+    FORMATS
+        .iter()
+        .find(|format| format.mime.eq_ignore_ascii_case(mime))
+        .map_or(&[], |format| format.extensions)
+}
+
+/// Tipo MIME associado a uma extensão conhecida (sem o ponto, sem distinção
+/// de maiúsculas)
+///
+/// # Exemplos
+/// ```rust
+/// use archivus::mime_for_extension;
+///
+/// assert_eq!(mime_for_extension("PNG"), Some("image/png"));
+/// assert_eq!(mime_for_extension("rs"), None);
+/// ```
+pub fn mime_for_extension(extension: &str) -> Option<&'static str> {
+    FORMATS
+        .iter()
+        .find(|format| format.extensions.iter().any(|ext| ext.eq_ignore_ascii_case(extension)))
+        .map(|format| format.mime)
+}
+
+/// Lê o início de um arquivo e detecta o tipo MIME
+pub(crate) fn sniff_file(path: &Path) -> io::Result<&'static str> {
+    let mut data = Vec::with_capacity(SNIFF_LEN);
+    File::open(path)?.take(SNIFF_LEN as u64).read_to_end(&mut data)?;
+    Ok(detect_mime(&data))
+}
+
+// UTF-16 com BOM, ou UTF-8 sem NUL e outros caracteres de controle além de
+// tabulação, quebras de linha, form feed e escape
+fn is_text(data: &[u8]) -> bool {
+    if data.starts_with(b"\xff\xfe") || data.starts_with(b"\xfe\xff") {
+        return data.len().is_multiple_of(2);
+    }
+
+    let data = data.strip_prefix(b"\xef\xbb\xbf").unwrap_or(data);
+    let text = match std::str::from_utf8(data) {
+        Ok(text) => text,
+        // Caractere cortado no fim do trecho lido
+        Err(e) if e.error_len().is_none() && data.len() - e.valid_up_to() < 4 => {
+            std::str::from_utf8(&data[..e.valid_up_to()]).unwrap_or_default()
+        }
+        Err(_) => return false,
+    };
+    !text.chars().any(|c| c.is_control() && !matches!(c, '\t' | '\n' | '\r' | '\x0c' | '\x1b'))
+}
+
+/// Arquivo cujo conteúdo não corresponde à extensão, encontrado por
+/// `Archivus::find_extension_mismatches`
+#[derive(Debug, Clone)]
+pub struct ExtensionMismatch {
+    /// Arquivo examinado
+    pub file: FileInfo,
+    /// Tipo detectado pelo conteúdo
+    pub detected: &'static str,
+    /// Tipo associado à extensão
+    pub expected: &'static str,
+}
+
+impl ExtensionMismatch {
+    // Compara o conteúdo com a extensão; extensões desconhecidas, arquivos
+    // vazios, itens que não são arquivos comuns (um pipe bloquearia a
+    // leitura) e arquivos ilegíveis não são avaliados
+    pub(crate) fn check(file: FileInfo) -> Option<Self> {
+        if !file.is_file {
+            return None;
+        }
+        let expected = file.extension.as_deref().and_then(mime_for_extension)?;
+        let detected = sniff_file(&file.path).ok()?;
+        let extension = file.extension.as_deref().unwrap_or_default();
+        if detected == MIME_EMPTY || extensions_for_mime(detected).iter().any(|ext| ext.eq_ignore_ascii_case(extension)) {
+            return None;
+        }
+        Some(Self { file, detected, expected })
+    }
+}
+
+// ====================================================================
+// TESTES UNITÁRIOS
+// ====================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_mime() {
+        assert_eq!(detect_mime(b"\xff\xd8\xff\xe0\0\x10JFIF"), "image/jpeg");
+        assert_eq!(detect_mime(b"GIF89a\x01\0"), "image/gif");
+        assert_eq!(detect_mime(b"%PDF-1.7\n"), "application/pdf");
+        assert_eq!(detect_mime(b"PK\x03\x04\x14\0"), "application/zip");
+        assert_eq!(detect_mime(b"\x1f\x8b\x08\0"), "application/gzip");
+        assert_eq!(detect_mime(b"\x7fELF\x02\x01\x01"), "application/x-executable");
+        assert_eq!(detect_mime(b"SQLite format 3\0\x10\0"), "application/vnd.sqlite3");
+        assert_eq!(detect_mime(b"RIFF\0\0\0\0WEBPVP8 "), "image/webp");
+        assert_eq!(detect_mime(b"RIFF\0\0\0\0WAVEfmt "), "audio/wav");
+        assert_eq!(detect_mime(b"\0\0\0\x18ftypisom"), "video/mp4");
+
+        let mut tar = vec![0u8; 512];
+        tar[..8].copy_from_slice(b"arquivo\0");
+        tar[257..263].copy_from_slice(b"ustar\0");
+        assert_eq!(detect_mime(&tar), "application/x-tar");
+
+        // Texto: UTF-8 (com caractere cortado no fim) e UTF-16 com BOM
+        assert_eq!(detect_mime(b""), MIME_EMPTY);
+        assert_eq!(detect_mime("ação\tfeita\r\n".as_bytes()), MIME_TEXT);
+        assert_eq!(detect_mime(&"ação".as_bytes()[..3]), MIME_TEXT);
+        assert_eq!(detect_mime(b"\xef\xbb\xbfcom BOM"), MIME_TEXT);
+        assert_eq!(detect_mime(b"\xff\xfeo\0i\0"), MIME_TEXT);
+        assert_eq!(detect_mime(b"texto\0com NUL"), MIME_BINARY);
+        assert_eq!(detect_mime(b"\xc3\x28 invalido"), MIME_BINARY);
+
+        assert_eq!(mime_for_extension("Jpeg"), Some("image/jpeg"));
+        assert_eq!(mime_for_extension("docx"), Some("application/zip"));
+        assert!(extensions_for_mime("IMAGE/PNG").contains(&"png"));
+    }
+
+    #[test]
+    fn test_mismatch_unreadable() {
+        let root = crate::tests::scratch_dir("mismatch_unreadable");
+        let path = root.join("foto.jpg");
+        std::fs::write(&path, b"\x89PNG\r\n\x1a\n").unwrap();
+        let file = FileInfo::new(&path).unwrap();
+        assert_eq!(ExtensionMismatch::check(file.clone()).map(|m| m.detected), Some("image/png"));
+
+        // Removido depois da listagem: ignorado, sem erro
+        std::fs::remove_file(&path).unwrap();
+        assert!(ExtensionMismatch::check(file).is_none());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,361 @@
+// ====================================================================
+// PERCURSO PARALELO DE DIRETÓRIOS
+// ====================================================================
+// Percorre uma árvore com várias threads, distribuindo os diretórios
+// entre filas com roubo de trabalho (work stealing)
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use crate::walk::{drain_partial, read_dir_entries, DirEntries, DirId, Failure, PendingDir, Traversal};
+use crate::{ArchivusError, FileFilter, FileInfo};
+
+// Quantidade de resultados que podem aguardar o consumidor antes que as
+// threads parem de ler novas entradas
+const CHANNEL_CAPACITY: usize = 1024;
+
+// Tempo máximo que uma thread ociosa espera antes de procurar trabalho de novo
+const IDLE_WAIT: Duration = Duration::from_millis(10);
+
+type Item = Result<FileInfo, Failure>;
+
+// Diretório a ser lido por uma das threads
+struct Job {
+    dir: PendingDir,
+    // Entradas já abertas (apenas para a raiz)
+    entries: Option<DirEntries>,
+    // Diretórios entre a raiz e este, inclusive, para detectar ciclos
+    ancestors: Arc<Vec<DirId>>,
+}
+
+// Estado compartilhado entre as threads
+struct Shared {
+    traversal: Traversal,
+    queues: Vec<Mutex<VecDeque<Job>>>,
+    // Diretórios enfileirados ou em processamento
+    pending: AtomicUsize,
+    abort: AtomicBool,
+    idle: Mutex<()>,
+    wakeup: Condvar,
+}
+
+impl Shared {
+    fn push(&self, worker: usize, job: Job) {
+        self.pending.fetch_add(1, Ordering::SeqCst);
+        self.queues[worker].lock().unwrap().push_back(job);
+        self.wakeup.notify_one();
+    }
+
+    // Pega o diretório mais recente da própria fila ou rouba o mais antigo
+    // da fila de outra thread
+    fn pop(&self, worker: usize) -> Option<Job> {
+        if let Some(job) = self.queues[worker].lock().unwrap().pop_back() {
+            return Some(job);
+        }
+
+        let count = self.queues.len();
+        (1..count)
+            .map(|offset| (worker + offset) % count)
+            .find_map(|victim| self.queues[victim].lock().unwrap().pop_front())
+    }
+
+    fn finish_job(&self) {
+        if self.pending.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.wakeup.notify_all();
+        }
+    }
+
+    // Envia um resultado; retorna false se o consumidor desistiu
+    fn send(&self, tx: &SyncSender<Item>, item: Item) -> bool {
+        if tx.send(item).is_err() {
+            self.abort.store(true, Ordering::SeqCst);
+            return false;
+        }
+        true
+    }
+
+    fn run_worker(&self, worker: usize, tx: SyncSender<Item>) {
+        while !self.abort.load(Ordering::SeqCst) {
+            if let Some(job) = self.pop(worker) {
+                self.process(worker, job, &tx);
+                self.finish_job();
+            } else if self.pending.load(Ordering::SeqCst) == 0 {
+                return;
+            } else {
+                let guard = self.idle.lock().unwrap();
+                let _ = self.wakeup.wait_timeout(guard, IDLE_WAIT);
+            }
+        }
+    }
+
+    fn process(&self, worker: usize, job: Job, tx: &SyncSender<Item>) {
+        let entries = match job.entries {
+            Some(entries) => entries,
+            None => match read_dir_entries(&job.dir.path, false) {
+                Ok(entries) => entries,
+                Err(failure) => {
+                    self.send(tx, Err(failure));
+                    return;
+                }
+            },
+        };
+        let ignore = self.traversal.ignore_rules(&job.dir);
+
+        for entry in entries {
+            if self.abort.load(Ordering::SeqCst) {
+                return;
+            }
+
+            let item = match entry {
+                Ok(path) => self.traversal.read_entry(path, job.dir.depth),
+                Err(e) => Err((job.dir.path.clone(), e.into())),
+            };
+
+            let file_info = match item {
+                Ok(file_info) => file_info,
+                Err(failure) => {
+                    if !self.send(tx, Err(failure)) {
+                        return;
+                    }
+                    continue;
+                }
+            };
+
+            if self.traversal.is_ignored(ignore.as_deref(), &file_info) {
+                continue;
+            }
+
+            if let Some(dir) = self.traversal.descend_into(&file_info, ignore.as_ref(), |id| job.ancestors.contains(id)) {
+                let ancestors = if dir.id.is_some() {
+                    let mut ancestors = (*job.ancestors).clone();
+                    ancestors.extend(dir.id.iter().cloned());
+                    Arc::new(ancestors)
+                } else {
+                    Arc::clone(&job.ancestors)
+                };
+                self.push(worker, Job { dir, entries: None, ancestors });
+            }
+
+            if self.traversal.filter.matches(&file_info) && !self.send(tx, Ok(file_info)) {
+                return;
+            }
+        }
+    }
+}
+
+// Caminho usado para ordenar resultados e falhas
+fn item_path(item: &Item) -> &Path {
+    match item {
+        Ok(file_info) => &file_info.path,
+        Err((path, _)) => path,
+    }
+}
+
+/// Percorre um diretório com várias threads
+///
+/// Produz os mesmos itens que o `Walker` para o mesmo `FileFilter`
+/// (profundidade, links simbólicos e detecção de ciclos incluídos), mas lê
+/// vários diretórios ao mesmo tempo, o que compensa a latência de volumes
+/// de rede. Cada thread trabalha na sua própria fila de diretórios e rouba
+/// trabalho das outras quando fica sem.
+///
+/// O número de threads vem de `FileFilter::threads` (0 usa o número de
+/// núcleos disponíveis). Sem `FileFilter::sorted`, a ordem dos itens
+/// depende do escalonamento das threads; com `sorted`, todos os itens são
+/// reunidos e produzidos em ordem de caminho, a mesma do `Walker` com
+/// `sorted`.
+///
+/// As threads são iniciadas na primeira chamada a `next()`. Descartar o
+/// iterador antes do fim interrompe o percurso.
+///
+/// # Exemplos
+/// ```rust,no_run
+/// use archivus::{Archivus, FileFilter};
+///
+/// let utils = Archivus::new();
+/// let filter = FileFilter {
+///     recursive: true,
+///     threads: 16,
+///     ..Default::default()
+/// };
+///
+/// let total: u64 = utils.walk_parallel("/mnt/nfs/dados", &filter)
+///     .filter_map(Result::ok)
+///     .map(|f| f.size)
+///     .sum();
+/// ```
+pub struct ParallelWalker {
+    root: Option<PathBuf>,
+    filter: FileFilter,
+    receiver: Option<Receiver<Item>>,
+    shared: Option<Arc<Shared>>,
+    sorted_items: Option<std::vec::IntoIter<Item>>,
+}
+
+impl ParallelWalker {
+    /// Cria um novo ParallelWalker a partir de um diretório raiz
+    ///
+    /// Como no `Walker`, a raiz não é produzida, apenas o seu conteúdo.
+    pub fn new<P: AsRef<Path>>(root: P, filter: &FileFilter) -> Self {
+        Self {
+            root: Some(root.as_ref().to_path_buf()),
+            filter: filter.clone(),
+            receiver: None,
+            shared: None,
+            sorted_items: None,
+        }
+    }
+
+    fn thread_count(&self) -> usize {
+        match self.filter.threads {
+            0 => thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+            n => n,
+        }
+    }
+
+    // Abre a raiz na thread atual e inicia as threads de trabalho
+    fn start(&mut self, root: PathBuf) -> Result<(), Failure> {
+        let mut traversal = Traversal::new(&self.filter);
+        let dir = traversal.root(&root);
+        let entries = read_dir_entries(&dir.path, false)?;
+        let ancestors = Arc::new(dir.id.iter().cloned().collect());
+
+        let threads = self.thread_count();
+        let shared = Arc::new(Shared {
+            traversal,
+            queues: (0..threads).map(|_| Mutex::new(VecDeque::new())).collect(),
+            pending: AtomicUsize::new(0),
+            abort: AtomicBool::new(false),
+            idle: Mutex::new(()),
+            wakeup: Condvar::new(),
+        });
+        shared.push(0, Job { dir, entries: Some(entries), ancestors });
+
+        let (tx, rx) = mpsc::sync_channel(CHANNEL_CAPACITY);
+        for worker in 0..threads {
+            let shared = Arc::clone(&shared);
+            let tx = tx.clone();
+            thread::spawn(move || shared.run_worker(worker, tx));
+        }
+        drop(tx);
+
+        if self.filter.sorted {
+            let mut items: Vec<Item> = rx.iter().collect();
+            items.sort_by(|a, b| item_path(a).cmp(item_path(b)));
+            self.sorted_items = Some(items.into_iter());
+        } else {
+            self.receiver = Some(rx);
+        }
+        self.shared = Some(shared);
+        Ok(())
+    }
+
+    fn next_entry(&mut self) -> Option<Item> {
+        if let Some(root) = self.root.take()
+            && let Err(failure) = self.start(root)
+        {
+            return Some(Err(failure));
+        }
+
+        match &mut self.sorted_items {
+            Some(items) => items.next(),
+            None => self.receiver.as_ref()?.recv().ok(),
+        }
+    }
+
+    /// Versão paralela de `Walker::for_each_partial`
+    pub(crate) fn for_each_partial<F: FnMut(FileInfo)>(mut self, f: F) -> Result<Vec<Failure>, ArchivusError> {
+        if let Some(root) = self.root.take() {
+            self.start(root).map_err(|(_, e)| e)?;
+        }
+        Ok(drain_partial(std::iter::from_fn(|| self.next_entry()), f))
+    }
+}
+
+impl Iterator for ParallelWalker {
+    type Item = Result<FileInfo, ArchivusError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_entry().map(|item| item.map_err(|(_, e)| e))
+    }
+}
+
+impl Drop for ParallelWalker {
+    fn drop(&mut self) {
+        if let Some(shared) = &self.shared {
+            shared.abort.store(true, Ordering::SeqCst);
+        }
+    }
+}
+
+// ====================================================================
+// TESTES UNITÁRIOS
+// ====================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::scratch_dir;
+    use crate::Walker;
+    use std::fs;
+
+    fn build_tree(root: &Path) {
+        for a in 0..4 {
+            for b in 0..3 {
+                let dir = root.join(format!("d{}/e{}", a, b));
+                fs::create_dir_all(&dir).unwrap();
+                for c in 0..5 {
+                    fs::write(dir.join(format!("f{}.txt", c)), vec![b'x'; c]).unwrap();
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_parallel_matches_sequential() {
+        let root = scratch_dir("parallel_matches");
+        build_tree(&root);
+
+        let filter = FileFilter {
+            recursive: true,
+            sorted: true,
+            threads: 4,
+            min_depth: Some(2),
+            ..Default::default()
+        };
+        let key = |f: FileInfo| (f.path, f.depth, f.size);
+        let sequential: Vec<_> = Walker::new(&root, &filter).map(|r| key(r.unwrap())).collect();
+        let parallel: Vec<_> = ParallelWalker::new(&root, &filter).map(|r| key(r.unwrap())).collect();
+
+        assert_eq!(sequential.len(), 4 * 3 + 4 * 3 * 5);
+        assert_eq!(sequential, parallel);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_parallel_unsorted_and_early_drop() {
+        let root = scratch_dir("parallel_unsorted");
+        build_tree(&root);
+
+        let filter = FileFilter {
+            recursive: true,
+            include_directories: false,
+            threads: 3,
+            ..Default::default()
+        };
+        assert_eq!(ParallelWalker::new(&root, &filter).count(), 60);
+        assert_eq!(ParallelWalker::new(&root, &filter).take(2).count(), 2);
+
+        let mut missing = ParallelWalker::new(root.join("inexistente"), &filter);
+        assert!(matches!(missing.next(), Some(Err(ArchivusError::NotFound(_)))));
+        assert!(missing.next().is_none());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,250 @@
+// ====================================================================
+// PADRÕES DE NOME (* e ?)
+// ====================================================================
+// Padrões simples de nome compilados uma única vez e comparados em tempo
+// linear, sem retrocesso exponencial
+
+use std::fmt;
+
+// Bits por palavra dos conjuntos de estados
+const WORD_BITS: usize = 64;
+
+// Trecho do padrão entre dois `*`: caracteres literais e `?`
+#[derive(Debug, Clone)]
+struct Chunk {
+    // `None` representa `?`
+    chars: Vec<Option<char>>,
+    // Para cada caractere do trecho, as posições em que ele (ou `?`) aparece,
+    // ordenado por caractere para busca binária
+    masks: Vec<(char, Vec<u64>)>,
+    // Posições ocupadas por `?`, que aceitam qualquer caractere
+    any: Vec<u64>,
+}
+
+impl Chunk {
+    fn new(chars: Vec<Option<char>>) -> Self {
+        let words = chars.len().div_ceil(WORD_BITS).max(1);
+        let mut any = vec![0u64; words];
+        for (i, c) in chars.iter().enumerate() {
+            if c.is_none() {
+                any[i / WORD_BITS] |= 1 << (i % WORD_BITS);
+            }
+        }
+
+        let mut masks: Vec<(char, Vec<u64>)> = Vec::new();
+        for (i, c) in chars.iter().enumerate() {
+            let Some(c) = *c else { continue };
+            let index = match masks.binary_search_by(|(m, _)| m.cmp(&c)) {
+                Ok(index) => index,
+                Err(index) => {
+                    masks.insert(index, (c, any.clone()));
+                    index
+                }
+            };
+            masks[index].1[i / WORD_BITS] |= 1 << (i % WORD_BITS);
+        }
+
+        Self { chars, masks, any }
+    }
+
+    fn len(&self) -> usize {
+        self.chars.len()
+    }
+
+    fn mask(&self, c: char) -> &[u64] {
+        match self.masks.binary_search_by(|(m, _)| m.cmp(&c)) {
+            Ok(index) => &self.masks[index].1,
+            Err(_) => &self.any,
+        }
+    }
+
+    // Compara o trecho com o texto a partir de uma posição fixa
+    fn matches_at(&self, text: &[char], start: usize) -> bool {
+        text.len() >= start + self.len()
+            && self.chars.iter().zip(&text[start..]).all(|(p, t)| p.is_none_or(|p| p == *t))
+    }
+
+    // Procura a primeira ocorrência do trecho em `text` (algoritmo Shift-And,
+    // que lê cada caractere do texto uma única vez) e retorna a posição logo
+    // após ela
+    fn find_end(&self, text: &[char]) -> Option<usize> {
+        if self.chars.is_empty() {
+            return Some(0);
+        }
+
+        let last = self.len() - 1;
+        let mut state = vec![0u64; self.any.len()];
+        for (i, &c) in text.iter().enumerate() {
+            // state = ((state << 1) | 1) & mask(c)
+            let mut carry = 1;
+            for (word, mask) in state.iter_mut().zip(self.mask(c)) {
+                let next_carry = *word >> (WORD_BITS - 1);
+                *word = ((*word << 1) | carry) & mask;
+                carry = next_carry;
+            }
+
+            if state[last / WORD_BITS] & (1 << (last % WORD_BITS)) != 0 {
+                return Some(i + 1);
+            }
+        }
+        None
+    }
+}
+
+/// Padrão de nome compilado, com `*` (qualquer sequência) e `?` (um caractere)
+///
+/// O padrão é analisado uma única vez e pode ser reutilizado para comparar
+/// muitos nomes. A comparação lê cada caractere do nome um número constante
+/// de vezes, então padrões como `*a*a*a*a*b` não degradam com nomes longos.
+///
+/// Diferente de `Glob`, não há separadores de caminho: `*` corresponde a
+/// qualquer caractere, inclusive `/`.
+///
+/// # Exemplos
+/// ```rust
+/// use archivus::Pattern;
+///
+/// let pattern = Pattern::new_ignore_case("relatorio_*.PDF");
+///
+/// assert!(pattern.matches("Relatorio_2024.pdf"));
+/// assert!(!pattern.matches("relatorio.pdf"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    pattern: String,
+    ignore_case: bool,
+    // Trechos entre os `*`; sem nenhum `*`, há um único trecho
+    chunks: Vec<Chunk>,
+}
+
+impl Pattern {
+    /// Compila um padrão que diferencia maiúsculas de minúsculas
+    pub fn new(pattern: &str) -> Self {
+        Self::compile(pattern, false)
+    }
+
+    /// Compila um padrão que ignora maiúsculas e minúsculas
+    pub fn new_ignore_case(pattern: &str) -> Self {
+        Self::compile(pattern, true)
+    }
+
+    fn compile(pattern: &str, ignore_case: bool) -> Self {
+        let normalized = if ignore_case { pattern.to_lowercase() } else { pattern.to_string() };
+
+        let chunks = normalized
+            .split('*')
+            .map(|chunk| Chunk::new(chunk.chars().map(|c| (c != '?').then_some(c)).collect()))
+            .collect();
+
+        Self {
+            pattern: pattern.to_string(),
+            ignore_case,
+            chunks,
+        }
+    }
+
+    /// Retorna o padrão original
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Verifica se o padrão ignora maiúsculas e minúsculas
+    pub fn is_ignore_case(&self) -> bool {
+        self.ignore_case
+    }
+
+    /// Verifica se o texto inteiro corresponde ao padrão
+    pub fn matches(&self, text: &str) -> bool {
+        let text: Vec<char> = if self.ignore_case {
+            text.chars().flat_map(char::to_lowercase).collect()
+        } else {
+            text.chars().collect()
+        };
+
+        let (first, rest) = self.chunks.split_first().expect("split sempre gera um trecho");
+        let Some((last, middle)) = rest.split_last() else {
+            // Sem `*`: o texto deve ter exatamente o tamanho do trecho
+            return text.len() == first.len() && first.matches_at(&text, 0);
+        };
+
+        // O primeiro trecho fica preso ao início e o último ao final; os do
+        // meio são procurados da esquerda para a direita. Escolher sempre a
+        // ocorrência mais à esquerda nunca impede uma correspondência.
+        if text.len() < first.len() + last.len()
+            || !first.matches_at(&text, 0)
+            || !last.matches_at(&text, text.len() - last.len())
+        {
+            return false;
+        }
+
+        let mut window = &text[first.len()..text.len() - last.len()];
+        for chunk in middle {
+            match chunk.find_end(window) {
+                Some(end) => window = &window[end..],
+                None => return false,
+            }
+        }
+        true
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.pattern)
+    }
+}
+
+// ====================================================================
+// TESTES UNITÁRIOS
+// ====================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pattern_matches() {
+        let cases = [
+            ("*", "", true),
+            ("*", "qualquer", true),
+            ("", "", true),
+            ("", "a", false),
+            ("abc", "abc", true),
+            ("abc", "abcd", false),
+            ("a?c", "abc", true),
+            ("a?c", "ac", false),
+            ("*.txt", "notas.txt", true),
+            ("*.txt", "notas.txt.bak", false),
+            ("a*b*c", "abc", true),
+            ("a*b*c", "aXbYbZc", true),
+            ("a*b*c", "acb", false),
+            ("a*a", "a", false),
+            ("**a**", "bab", true),
+            ("*?*?", "ab", true),
+            ("*?*?", "a", false),
+            ("ação*", "ação.txt", true),
+        ];
+
+        for (pattern, text, expected) in cases {
+            assert_eq!(Pattern::new(pattern).matches(text), expected, "{:?} ~ {:?}", pattern, text);
+        }
+
+        assert!(!Pattern::new("*.TXT").matches("a.txt"));
+        assert!(Pattern::new_ignore_case("*.TXT").matches("A.txt"));
+        assert_eq!(Pattern::new_ignore_case("*.TXT").to_string(), "*.TXT");
+    }
+
+    #[test]
+    fn test_pattern_long_inputs() {
+        // Casos que travavam o algoritmo recursivo anterior
+        let text = "a".repeat(10_000);
+        assert!(!Pattern::new("*a*a*a*a*a*a*a*a*b").matches(&text));
+        assert!(Pattern::new("*a*a*a*a*a*a*a*a*a").matches(&text));
+
+        // Trechos com mais de 64 caracteres usam várias palavras de estado
+        let chunk = format!("{}?b", "a".repeat(70));
+        let text = format!("x{}cbx", "a".repeat(70));
+        assert!(Pattern::new(&format!("*{}*", chunk)).matches(&text));
+        assert!(!Pattern::new(&format!("*{}*", chunk)).matches(&text.replace("cb", "cc")));
+    }
+}
@@ -0,0 +1,442 @@
+// ====================================================================
+// PREDICADOS COMBINÁVEIS
+// ====================================================================
+// Critérios sobre FileInfo que podem ser combinados com E, OU e NÃO,
+// além dos campos fixos do FileFilter
+
+use std::fmt;
+use std::sync::Arc;
+
+use crate::{FileFilter, FileInfo, ModeFilter, Pattern, Regex, TimeSpec};
+
+/// Critério que decide se um item entra no resultado
+///
+/// Predicados podem ser combinados com `and`, `or` e `not`. Qualquer closure
+/// `Fn(&FileInfo) -> bool` também é um predicado, e `Criterion` oferece um
+/// predicado pronto para cada critério do `FileFilter`.
+///
+/// # Exemplos
+/// ```rust,no_run
+/// use archivus::{Archivus, Criterion, FileInfo, Predicate};
+///
+/// // Arquivos .log com mais de 10 MB ou qualquer arquivo .core
+/// let predicate = Criterion::extensions(&["log"])
+///     .and(Criterion::min_size(10 * 1024 * 1024))
+///     .or(|f: &FileInfo| f.extension.as_deref() == Some("core"));
+///
+/// let utils = Archivus::new();
+/// let found = utils.list_with_filter("/var/log", predicate);
+/// ```
+pub trait Predicate: Send + Sync {
+    /// Verifica se o item atende ao critério
+    fn matches(&self, file_info: &FileInfo) -> bool;
+
+    /// Descrição na linguagem de consulta de `FileFilter::parse`, usada ao
+    /// escrever um `FileFilter` como texto; `None` se não houver
+    fn describe(&self) -> Option<String> {
+        None
+    }
+
+    /// Descrição da negação deste predicado, usada por `Not::describe`;
+    /// `None` se a linguagem de consulta não puder expressá-la
+    fn describe_negated(&self) -> Option<String> {
+        None
+    }
+
+    /// Atende a este critério e também a `other`
+    fn and<P: Predicate>(self, other: P) -> And<Self, P>
+    where
+        Self: Sized,
+    {
+        And(self, other)
+    }
+
+    /// Atende a este critério ou a `other`
+    fn or<P: Predicate>(self, other: P) -> Or<Self, P>
+    where
+        Self: Sized,
+    {
+        Or(self, other)
+    }
+
+    /// Não atende a este critério
+    fn not(self) -> Not<Self>
+    where
+        Self: Sized,
+    {
+        Not(self)
+    }
+}
+
+impl<F> Predicate for F
+where
+    F: Fn(&FileInfo) -> bool + Send + Sync,
+{
+    fn matches(&self, file_info: &FileInfo) -> bool {
+        self(file_info)
+    }
+}
+
+// Permite `#[derive(Debug)]` em estruturas com `Arc<dyn Predicate>`
+impl fmt::Debug for dyn Predicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Predicate")
+    }
+}
+
+/// Combinação de dois predicados com E, criada por `Predicate::and`
+#[derive(Debug, Clone)]
+pub struct And<A, B>(A, B);
+
+impl<A: Predicate, B: Predicate> Predicate for And<A, B> {
+    fn matches(&self, file_info: &FileInfo) -> bool {
+        self.0.matches(file_info) && self.1.matches(file_info)
+    }
+
+    // Termos justapostos na consulta já são combinados com E
+    fn describe(&self) -> Option<String> {
+        Some(format!("{} {}", self.0.describe()?, self.1.describe()?))
+    }
+}
+
+/// Combinação de dois predicados com OU, criada por `Predicate::or`
+#[derive(Debug, Clone)]
+pub struct Or<A, B>(A, B);
+
+// A linguagem de consulta não tem OU: `describe` retorna `None`
+impl<A: Predicate, B: Predicate> Predicate for Or<A, B> {
+    fn matches(&self, file_info: &FileInfo) -> bool {
+        self.0.matches(file_info) || self.1.matches(file_info)
+    }
+}
+
+/// Negação de um predicado, criada por `Predicate::not`
+#[derive(Debug, Clone)]
+pub struct Not<P>(P);
+
+impl<P: Predicate> Predicate for Not<P> {
+    fn matches(&self, file_info: &FileInfo) -> bool {
+        !self.0.matches(file_info)
+    }
+
+    fn describe(&self) -> Option<String> {
+        self.0.describe_negated()
+    }
+
+    fn describe_negated(&self) -> Option<String> {
+        self.0.describe()
+    }
+}
+
+/// Predicados prontos, um para cada critério do `FileFilter`
+///
+/// Cada um se comporta exatamente como o campo correspondente do filtro.
+/// Opções de percurso (`recursive`, `threads`, `symlinks`,
+/// `prune_directories`...) não são critérios e não têm predicado.
+#[derive(Debug, Clone)]
+pub struct Criterion(FileFilter);
+
+impl Criterion {
+    fn with(filter: FileFilter) -> Self {
+        Criterion(filter)
+    }
+
+    /// Arquivos com uma das extensões (sem o ponto); diretórios passam
+    pub fn extensions(extensions: &[&str]) -> Self {
+        Self::with(FileFilter {
+            extensions: Some(extensions.iter().map(|ext| ext.to_string()).collect()),
+            ..Default::default()
+        })
+    }
+
+    /// Arquivos sem nenhuma das extensões; diretórios passam
+    pub fn exclude_extensions(extensions: &[&str]) -> Self {
+        Self::with(FileFilter {
+            exclude_extensions: Some(extensions.iter().map(|ext| ext.to_string()).collect()),
+            ..Default::default()
+        })
+    }
+
+    /// Nome corresponde a um dos padrões
+    pub fn names(patterns: Vec<Pattern>) -> Self {
+        Self::with(FileFilter {
+            names: Some(patterns),
+            ..Default::default()
+        })
+    }
+
+    /// Nome não corresponde a nenhum dos padrões
+    pub fn exclude_names(patterns: Vec<Pattern>) -> Self {
+        Self::with(FileFilter {
+            exclude_names: Some(patterns),
+            ..Default::default()
+        })
+    }
+
+    /// Arquivos cujo tipo MIME, detectado pelo conteúdo, corresponde a um dos
+    /// padrões; diretórios passam
+    pub fn mime_types(patterns: Vec<Pattern>) -> Self {
+        Self::with(FileFilter {
+            mime_types: Some(patterns),
+            ..Default::default()
+        })
+    }
+
+    /// Tamanho de pelo menos `size` bytes
+    pub fn min_size(size: u64) -> Self {
+        Self::with(FileFilter {
+            min_size: Some(size),
+            ..Default::default()
+        })
+    }
+
+    /// Tamanho de no máximo `size` bytes
+    pub fn max_size(size: u64) -> Self {
+        Self::with(FileFilter {
+            max_size: Some(size),
+            ..Default::default()
+        })
+    }
+
+    /// Permissões atendem ao critério
+    pub fn mode(mode: ModeFilter) -> Self {
+        Self::with(FileFilter {
+            mode: Some(mode),
+            ..Default::default()
+        })
+    }
+
+    /// Dono é `uid`
+    pub fn uid(uid: u32) -> Self {
+        Self::with(FileFilter {
+            uid: Some(uid),
+            ..Default::default()
+        })
+    }
+
+    /// Grupo é `gid`
+    pub fn gid(gid: u32) -> Self {
+        Self::with(FileFilter {
+            gid: Some(gid),
+            ..Default::default()
+        })
+    }
+
+    /// Apenas arquivos (`include_directories: false`)
+    pub fn files() -> Self {
+        Self::with(FileFilter {
+            include_directories: false,
+            ..Default::default()
+        })
+    }
+
+    /// Apenas diretórios (`include_files: false`)
+    pub fn directories() -> Self {
+        Self::with(FileFilter {
+            include_files: false,
+            ..Default::default()
+        })
+    }
+
+    /// Apenas itens que não são ocultos (`include_hidden: false`)
+    pub fn visible() -> Self {
+        Self::with(FileFilter {
+            include_hidden: false,
+            ..Default::default()
+        })
+    }
+
+    /// Profundidade de pelo menos `depth`
+    pub fn min_depth(depth: usize) -> Self {
+        Self::with(FileFilter {
+            min_depth: Some(depth),
+            ..Default::default()
+        })
+    }
+
+    /// Profundidade de no máximo `depth`
+    pub fn max_depth(depth: usize) -> Self {
+        Self::with(FileFilter {
+            max_depth: Some(depth),
+            ..Default::default()
+        })
+    }
+
+    /// Nome corresponde à expressão
+    pub fn name_regex(regex: Regex) -> Self {
+        Self::with(FileFilter {
+            name_regex: Some(regex),
+            ..Default::default()
+        })
+    }
+
+    /// Nome não corresponde à expressão
+    pub fn exclude_name_regex(regex: Regex) -> Self {
+        Self::with(FileFilter {
+            exclude_name_regex: Some(regex),
+            ..Default::default()
+        })
+    }
+
+    /// Caminho corresponde à expressão
+    pub fn path_regex(regex: Regex) -> Self {
+        Self::with(FileFilter {
+            path_regex: Some(regex),
+            ..Default::default()
+        })
+    }
+
+    /// Caminho não corresponde à expressão
+    pub fn exclude_path_regex(regex: Regex) -> Self {
+        Self::with(FileFilter {
+            exclude_path_regex: Some(regex),
+            ..Default::default()
+        })
+    }
+
+    /// Modificado em `time` ou depois
+    pub fn modified_after(time: TimeSpec) -> Self {
+        Self::with(FileFilter {
+            modified_after: Some(time),
+            ..Default::default()
+        })
+    }
+
+    /// Modificado antes de `time`
+    pub fn modified_before(time: TimeSpec) -> Self {
+        Self::with(FileFilter {
+            modified_before: Some(time),
+            ..Default::default()
+        })
+    }
+
+    /// Acessado em `time` ou depois
+    pub fn accessed_after(time: TimeSpec) -> Self {
+        Self::with(FileFilter {
+            accessed_after: Some(time),
+            ..Default::default()
+        })
+    }
+
+    /// Acessado antes de `time`
+    pub fn accessed_before(time: TimeSpec) -> Self {
+        Self::with(FileFilter {
+            accessed_before: Some(time),
+            ..Default::default()
+        })
+    }
+
+    /// Criado em `time` ou depois
+    pub fn created_after(time: TimeSpec) -> Self {
+        Self::with(FileFilter {
+            created_after: Some(time),
+            ..Default::default()
+        })
+    }
+
+    /// Criado antes de `time`
+    pub fn created_before(time: TimeSpec) -> Self {
+        Self::with(FileFilter {
+            created_before: Some(time),
+            ..Default::default()
+        })
+    }
+}
+
+impl Predicate for Criterion {
+    fn matches(&self, file_info: &FileInfo) -> bool {
+        self.0.matches(file_info)
+    }
+
+    fn describe(&self) -> Option<String> {
+        Some(self.0.to_string())
+    }
+
+    // `!termo` na consulta é o complemento exato do termo, exceto para
+    // `ext:` e `mime:`, cuja negação continua deixando passar os itens que
+    // não são arquivos, e para os termos que já são exclusões
+    fn describe_negated(&self) -> Option<String> {
+        let term = self.0.to_string();
+        let key: String = term.chars().take_while(char::is_ascii_lowercase).collect();
+        match key.as_str() {
+            "name" | "regex" | "path" | "type" | "size" | "depth" | "perm" | "uid" | "gid" | "modified" | "accessed" | "created" => {
+                Some(format!("!{}", term))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Filtro aceito por `Archivus::list_with_filter`: um `&FileFilter` ou
+/// qualquer `Predicate`
+///
+/// Um predicado é aplicado com as opções padrão de percurso (sem recursão).
+/// Para combinar um predicado com outras opções, use o campo
+/// `FileFilter::predicate`.
+pub trait IntoFilter {
+    /// Converte no `FileFilter` usado pela busca
+    fn into_filter(self) -> FileFilter;
+}
+
+impl IntoFilter for &FileFilter {
+    fn into_filter(self) -> FileFilter {
+        self.clone()
+    }
+}
+
+impl<P: Predicate + 'static> IntoFilter for P {
+    fn into_filter(self) -> FileFilter {
+        FileFilter {
+            predicate: Some(Arc::new(self)),
+            ..Default::default()
+        }
+    }
+}
+
+// ====================================================================
+// TESTES UNITÁRIOS
+// ====================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::file_info;
+
+    #[test]
+    fn test_predicate_combinators() {
+        let big_logs = Criterion::extensions(&["log"])
+            .and(Criterion::min_size(10 * 1024 * 1024))
+            .or(|f: &FileInfo| f.extension.as_deref() == Some("core"));
+
+        assert!(big_logs.matches(&file_info("app.log", 20 * 1024 * 1024)));
+        assert!(!big_logs.matches(&file_info("app.log", 10)));
+        assert!(big_logs.matches(&file_info("programa.core", 10)));
+        assert!(!big_logs.matches(&file_info("notas.txt", 20 * 1024 * 1024)));
+
+        let not_log = Criterion::extensions(&["log"]).not();
+        assert!(!not_log.matches(&file_info("app.log", 1)));
+        assert!(not_log.matches(&file_info("app.txt", 1)));
+
+        // Predicados compartilhados entre threads
+        let shared: Arc<dyn Predicate> = Arc::new(Criterion::max_size(5).and(Criterion::files()));
+        assert!(shared.matches(&file_info("a", 5)));
+        assert_eq!(format!("{:?}", shared), "Predicate");
+        assert_eq!(shared.describe().as_deref(), Some("size<=5 type:file"));
+    }
+
+    #[test]
+    fn test_predicate_describe() {
+        // `!ext:log` deixaria passar diretórios, que `not()` rejeita
+        assert_eq!(Criterion::extensions(&["log"]).not().describe(), None);
+        assert_eq!(Criterion::exclude_extensions(&["log"]).not().describe(), None);
+        assert_eq!(Criterion::visible().not().describe(), None);
+        assert_eq!(Criterion::min_size(5).or(Criterion::files()).describe(), None);
+
+        let not_small = Criterion::max_size(5).not();
+        assert_eq!(not_small.describe().as_deref(), Some("!size<=5"));
+        assert!(not_small.matches(&file_info("a", 6)) && !not_small.matches(&file_info("a", 5)));
+        assert_eq!(Criterion::names(vec![Pattern::new("*.tmp")]).not().describe().as_deref(), Some("!name:*.tmp"));
+        assert_eq!(Criterion::files().not().not().describe().as_deref(), Some("type:file"));
+        assert_eq!(Criterion::min_depth(2).not().and(Criterion::files()).describe().as_deref(), Some("!depth>=2 type:file"));
+    }
+}
@@ -0,0 +1,865 @@
+// ====================================================================
+// LINGUAGEM DE CONSULTA
+// ====================================================================
+// Converte consultas textuais como `ext:log,txt size>10MB modified<7d`
+// em FileFilter e um FileFilter de volta em texto
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::{ArchivusError, FileFilter, FileInfo, ModeFilter, Pattern, Predicate, Regex, TimeSpec};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+// Unidades de tamanho, da maior para a menor
+const SIZE_UNITS: &[(&str, u64)] = &[
+    ("TB", 1 << 40),
+    ("GB", 1 << 30),
+    ("MB", 1 << 20),
+    ("KB", 1 << 10),
+];
+
+// Unidades de duração, da maior para a menor
+const DURATION_UNITS: &[(&str, u64)] = &[
+    ("w", 7 * SECONDS_PER_DAY),
+    ("d", SECONDS_PER_DAY),
+    ("h", 60 * 60),
+    ("m", 60),
+    ("s", 1),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Colon,
+    Eq,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Op {
+    fn as_str(self) -> &'static str {
+        match self {
+            Op::Colon => ":",
+            Op::Eq => "=",
+            Op::Lt => "<",
+            Op::Le => "<=",
+            Op::Gt => ">",
+            Op::Ge => ">=",
+        }
+    }
+}
+
+// Termo da consulta: `[!]campo<op>valor` ou uma palavra isolada (`recursive`)
+struct Term {
+    negated: bool,
+    key: String,
+    key_pos: usize,
+    op: Option<Op>,
+    op_pos: usize,
+    value: String,
+    value_pos: usize,
+}
+
+impl Term {
+    fn error_at(&self, position: usize, message: String) -> ArchivusError {
+        ArchivusError::ParseError { position, message }
+    }
+
+    fn op_error(&self) -> ArchivusError {
+        let op = self.op.map_or("", Op::as_str);
+        self.error_at(self.op_pos, format!("operador '{}' inválido para '{}'", op, self.key))
+    }
+
+    fn value_error(&self, message: &str) -> ArchivusError {
+        self.error_at(self.value_pos, format!("{}: '{}'", message, self.value))
+    }
+
+    fn duplicate_error(&self) -> ArchivusError {
+        self.error_at(self.key_pos, format!("'{}' definido mais de uma vez", self.key))
+    }
+
+    // Valores separados por vírgula (`ext:log,txt`)
+    fn list(&self) -> Result<Vec<String>, ArchivusError> {
+        let items: Vec<String> = self.value.split(',').map(str::to_string).collect();
+        if items.iter().any(String::is_empty) {
+            return Err(self.value_error("lista com item vazio"));
+        }
+        Ok(items)
+    }
+
+    fn extensions(&self) -> Result<Vec<String>, ArchivusError> {
+        Ok(self.list()?.into_iter().map(|ext| ext.trim_start_matches('.').to_string()).collect())
+    }
+
+    fn patterns(&self) -> Result<Vec<Pattern>, ArchivusError> {
+        Ok(self.list()?.iter().map(|pattern| Pattern::new_ignore_case(pattern)).collect())
+    }
+
+    fn regex(&self) -> Result<Regex, ArchivusError> {
+        Regex::new(&self.value).map_err(|e| match e {
+            ArchivusError::InvalidPattern(message) => self.error_at(self.value_pos, message),
+            other => other,
+        })
+    }
+}
+
+// Atribui um campo que só pode aparecer uma vez na consulta
+fn set_once<T>(slot: &mut Option<T>, value: T, term: &Term) -> Result<(), ArchivusError> {
+    if slot.is_some() {
+        return Err(term.duplicate_error());
+    }
+    *slot = Some(value);
+    Ok(())
+}
+
+struct Lexer {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Lexer {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn next_term(&mut self) -> Result<Option<Term>, ArchivusError> {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+        if self.peek().is_none() {
+            return Ok(None);
+        }
+
+        let negated = self.eat('!');
+        let key_pos = self.pos;
+        while self.peek().is_some_and(|c| c.is_alphanumeric() || c == '_') {
+            self.pos += 1;
+        }
+        let key: String = self.chars[key_pos..self.pos].iter().collect::<String>().to_lowercase();
+        if key.is_empty() {
+            return Err(ArchivusError::ParseError {
+                position: key_pos,
+                message: "esperado o nome de um campo".to_string(),
+            });
+        }
+
+        let op_pos = self.pos;
+        let op = match self.peek() {
+            Some(':') => Some(Op::Colon),
+            Some('=') => Some(Op::Eq),
+            Some('<') => Some(Op::Lt),
+            Some('>') => Some(Op::Gt),
+            None => None,
+            Some(c) if c.is_whitespace() => None,
+            Some(c) => {
+                return Err(ArchivusError::ParseError {
+                    position: op_pos,
+                    message: format!("caractere inesperado '{}' após '{}'", c, key),
+                });
+            }
+        };
+
+        let Some(mut op) = op else {
+            let end = self.pos;
+            return Ok(Some(Term { negated, key, key_pos, op: None, op_pos, value: String::new(), value_pos: end }));
+        };
+        self.pos += 1;
+        if self.eat('=') {
+            op = match op {
+                Op::Lt => Op::Le,
+                Op::Gt => Op::Ge,
+                _ => {
+                    return Err(ArchivusError::ParseError {
+                        position: op_pos,
+                        message: format!("operador '{}=' inválido", op.as_str()),
+                    });
+                }
+            };
+        }
+
+        let value_pos = self.pos;
+        let value = self.read_value()?;
+        if value.is_empty() {
+            return Err(ArchivusError::ParseError {
+                position: value_pos,
+                message: format!("valor vazio para '{}'", key),
+            });
+        }
+
+        Ok(Some(Term { negated, key, key_pos, op: Some(op), op_pos, value, value_pos }))
+    }
+
+    // Valor até o próximo espaço, ou entre aspas com `\"` e `\\`
+    fn read_value(&mut self) -> Result<String, ArchivusError> {
+        let start = self.pos;
+        if !self.eat('"') {
+            while self.peek().is_some_and(|c| !c.is_whitespace()) {
+                self.pos += 1;
+            }
+            return Ok(self.chars[start..self.pos].iter().collect());
+        }
+
+        let mut value = String::new();
+        loop {
+            match self.peek() {
+                None => {
+                    return Err(ArchivusError::ParseError {
+                        position: start,
+                        message: "aspas não fechadas".to_string(),
+                    });
+                }
+                Some('"') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some('\\') if matches!(self.chars.get(self.pos + 1), Some('"' | '\\')) => {
+                    value.push(self.chars[self.pos + 1]);
+                    self.pos += 2;
+                }
+                Some(c) => {
+                    value.push(c);
+                    self.pos += 1;
+                }
+            }
+        }
+
+        if self.peek().is_some_and(|c| !c.is_whitespace()) {
+            return Err(ArchivusError::ParseError {
+                position: self.pos,
+                message: "esperado espaço após as aspas".to_string(),
+            });
+        }
+        Ok(value)
+    }
+}
+
+// Termo negado que não tem campo de exclusão no FileFilter: um filtro com
+// o único termo, aplicado apenas aos itens que o termo de fato examina
+// (`mime:` só examina arquivos e deixa passar os demais itens)
+#[derive(Debug, Clone)]
+struct ExcludedTerm {
+    filter: FileFilter,
+    files_only: bool,
+}
+
+#[derive(Debug, Clone)]
+struct Excluded(Vec<ExcludedTerm>);
+
+impl Predicate for Excluded {
+    fn matches(&self, file_info: &FileInfo) -> bool {
+        !self.0.iter().any(|term| (!term.files_only || file_info.is_file) && term.filter.matches(file_info))
+    }
+
+    fn describe(&self) -> Option<String> {
+        Some(self.0.iter().map(|term| format!("!{}", term.filter)).collect::<Vec<_>>().join(" "))
+    }
+}
+
+impl FileFilter {
+    /// Cria um filtro a partir de uma consulta textual
+    ///
+    /// A consulta é uma lista de termos separados por espaços, todos
+    /// obrigatórios:
+    ///
+    /// | Termo | Campo |
+    /// |-------|-------|
+    /// | `ext:log,txt` | `extensions` |
+    /// | `name:*.tmp,core` | `names` (sem distinção de maiúsculas) |
+    /// | `!ext:bak,tmp` / `!name:*~` | `exclude_extensions` / `exclude_names` |
+    /// | `prune:.git,node_modules` | `prune_directories` |
+    /// | `mime:image/*,application/pdf` | `mime_types` |
+    /// | `regex:^a\d+$` / `path:/tmp/` | `name_regex` / `path_regex` |
+    /// | `type:file` / `type:dir` | `include_directories` / `include_files` |
+    /// | `size>10MB`, `size<=1KB`, `size=0` | `min_size` / `max_size` |
+    /// | `depth>=2`, `depth<=3` | `min_depth` / `max_depth` |
+    /// | `perm:644`, `perm:-4000`, `perm:/022` | `mode` (exato, todos, algum) |
+    /// | `uid=1000`, `gid=100` | `uid` / `gid` |
+    /// | `modified<7d`, `accessed>30d`, `created>=2024-01-01` | datas |
+    /// | `!hidden` | `include_hidden: false` |
+    /// | `recursive` | `recursive` |
+    ///
+    /// Tamanhos aceitam as unidades `KB`, `MB`, `GB` e `TB` (base 1024).
+    /// Datas aceitam idades (`s`, `m`, `h`, `d`, `w`), em que `<7d` significa
+    /// "há menos de 7 dias", ou instantes absolutos (`2024-01-01`, em UTC, ou
+    /// `@1700000000`). Permissões são escritas em octal, com `-` para exigir
+    /// todos os bits e `/` para exigir algum deles, como em `find -perm`.
+    /// `!` antes de um termo o nega. Valores com espaços
+    /// podem ser escritos entre aspas.
+    ///
+    /// Erros retornam `ArchivusError::ParseError` com a posição (em
+    /// caracteres, a partir de 0) do trecho inválido.
+    ///
+    /// # Exemplos
+    /// ```rust
+    /// use archivus::FileFilter;
+    ///
+    /// let filter = FileFilter::parse("ext:log,txt size>10MB modified<7d !name:*.tmp")?;
+    /// assert_eq!(filter.min_size, Some(10 * 1024 * 1024 + 1));
+    ///
+    /// // Display produz uma consulta equivalente
+    /// let text = filter.to_string();
+    /// assert_eq!(FileFilter::parse(&text)?.to_string(), text);
+    /// # Ok::<(), archivus::ArchivusError>(())
+    /// ```
+    pub fn parse(query: &str) -> Result<Self, ArchivusError> {
+        let mut lexer = Lexer {
+            chars: query.chars().collect(),
+            pos: 0,
+        };
+        let mut filter = FileFilter::default();
+        let mut excluded = Vec::new();
+
+        while let Some(term) = lexer.next_term()? {
+            if !term.negated {
+                apply_term(&mut filter, &term)?;
+                continue;
+            }
+            if apply_exclusion(&mut filter, &term)? {
+                continue;
+            }
+
+            // Os demais termos negados viram um predicado
+            if term.key == "recursive" {
+                return Err(term.error_at(term.key_pos - 1, "'recursive' não pode ser negado".to_string()));
+            }
+            let mut single = FileFilter::default();
+            apply_term(&mut single, &term)?;
+            excluded.push(ExcludedTerm {
+                filter: single,
+                files_only: term.key == "mime",
+            });
+        }
+
+        if !excluded.is_empty() {
+            filter.predicate = Some(std::sync::Arc::new(Excluded(excluded)));
+        }
+        Ok(filter)
+    }
+}
+
+impl FromStr for FileFilter {
+    type Err = ArchivusError;
+
+    fn from_str(query: &str) -> Result<Self, Self::Err> {
+        FileFilter::parse(query)
+    }
+}
+
+// Aplica um termo negado que tem campo de exclusão próprio; retorna false
+// se o campo não existir
+fn apply_exclusion(filter: &mut FileFilter, term: &Term) -> Result<bool, ArchivusError> {
+    if term.key == "hidden" {
+        if term.op.is_some() {
+            return Err(term.op_error());
+        }
+        if !filter.include_hidden {
+            return Err(term.duplicate_error());
+        }
+        filter.include_hidden = false;
+        return Ok(true);
+    }
+
+    if !matches!(term.key.as_str(), "ext" | "name" | "regex" | "path") {
+        return Ok(false);
+    }
+    if term.op != Some(Op::Colon) {
+        return Err(term.op_error());
+    }
+
+    match term.key.as_str() {
+        "ext" => set_once(&mut filter.exclude_extensions, term.extensions()?, term)?,
+        "name" => set_once(&mut filter.exclude_names, term.patterns()?, term)?,
+        "regex" => set_once(&mut filter.exclude_name_regex, term.regex()?, term)?,
+        _ => set_once(&mut filter.exclude_path_regex, term.regex()?, term)?,
+    }
+    Ok(true)
+}
+
+fn apply_term(filter: &mut FileFilter, term: &Term) -> Result<(), ArchivusError> {
+    let op = match (term.key.as_str(), term.op) {
+        ("recursive", None) => {
+            if filter.recursive {
+                return Err(term.duplicate_error());
+            }
+            filter.recursive = true;
+            return Ok(());
+        }
+        ("recursive", Some(_)) => return Err(term.op_error()),
+        ("hidden", _) => {
+            return Err(term.error_at(term.key_pos, "itens ocultos já são incluídos; use '!hidden' para excluí-los".to_string()));
+        }
+        (_, None) => {
+            return Err(term.error_at(term.op_pos, format!("esperado ':', '=', '<' ou '>' após '{}'", term.key)));
+        }
+        (_, Some(op)) => op,
+    };
+
+    match term.key.as_str() {
+        "ext" | "name" | "prune" | "mime" | "regex" | "path" | "type" | "perm" if op != Op::Colon => Err(term.op_error()),
+        "uid" | "gid" if op != Op::Eq => Err(term.op_error()),
+        "ext" => set_once(&mut filter.extensions, term.extensions()?, term),
+        "name" => set_once(&mut filter.names, term.patterns()?, term),
+        "prune" => set_once(&mut filter.prune_directories, term.patterns()?, term),
+        "mime" => set_once(&mut filter.mime_types, term.patterns()?, term),
+        "regex" => set_once(&mut filter.name_regex, term.regex()?, term),
+        "path" => set_once(&mut filter.path_regex, term.regex()?, term),
+        "perm" => {
+            let mode = parse_mode(&term.value).ok_or_else(|| term.value_error("permissão inválida (use octal, ex.: 644, -4000 ou /022)"))?;
+            set_once(&mut filter.mode, mode, term)
+        }
+        "uid" | "gid" => {
+            let id = term.value.parse::<u32>().map_err(|_| term.value_error("identificador inválido"))?;
+            let slot = if term.key == "uid" { &mut filter.uid } else { &mut filter.gid };
+            set_once(slot, id, term)
+        }
+        "type" => {
+            let slot = match term.value.to_lowercase().as_str() {
+                "file" | "f" => &mut filter.include_directories,
+                "dir" | "directory" | "d" => &mut filter.include_files,
+                _ => return Err(term.value_error("tipo desconhecido (use file ou dir)")),
+            };
+            if !*slot {
+                return Err(term.duplicate_error());
+            }
+            *slot = false;
+            Ok(())
+        }
+        "size" => {
+            let size = parse_size(&term.value).ok_or_else(|| term.value_error("tamanho inválido"))?;
+            apply_range(&mut filter.min_size, &mut filter.max_size, size, op, term)
+        }
+        "depth" => {
+            let depth = term.value.parse::<u64>().map_err(|_| term.value_error("profundidade inválida"))?;
+            let (mut min, mut max) = (filter.min_depth.map(|d| d as u64), filter.max_depth.map(|d| d as u64));
+            apply_range(&mut min, &mut max, depth, op, term)?;
+            filter.min_depth = min.map(|d| d as usize);
+            filter.max_depth = max.map(|d| d as usize);
+            Ok(())
+        }
+        "modified" | "accessed" | "created" => {
+            let (after, before) = match term.key.as_str() {
+                "modified" => (&mut filter.modified_after, &mut filter.modified_before),
+                "accessed" => (&mut filter.accessed_after, &mut filter.accessed_before),
+                _ => (&mut filter.created_after, &mut filter.created_before),
+            };
+            apply_time(after, before, op, term)
+        }
+        _ => Err(term.error_at(term.key_pos, format!("campo desconhecido '{}'", term.key))),
+    }
+}
+
+// `>`/`>=`/`<`/`<=`/`=` sobre um intervalo inclusivo [min, max]
+fn apply_range(min: &mut Option<u64>, max: &mut Option<u64>, value: u64, op: Op, term: &Term) -> Result<(), ArchivusError> {
+    match op {
+        Op::Ge => set_once(min, value, term),
+        Op::Gt => set_once(min, value.checked_add(1).ok_or_else(|| term.value_error("valor grande demais"))?, term),
+        Op::Le => set_once(max, value, term),
+        Op::Lt => set_once(max, value.checked_sub(1).ok_or_else(|| term.value_error("nenhum valor é menor que 0"))?, term),
+        Op::Eq => {
+            set_once(min, value, term)?;
+            set_once(max, value, term)
+        }
+        Op::Colon => Err(term.op_error()),
+    }
+}
+
+fn apply_time(after: &mut Option<TimeSpec>, before: &mut Option<TimeSpec>, op: Op, term: &Term) -> Result<(), ArchivusError> {
+    if let Some(age) = parse_duration(&term.value) {
+        // Idades: `<7d` é mais recente que 7 dias atrás
+        return match op {
+            Op::Lt | Op::Le => set_once(after, TimeSpec::Ago(age), term),
+            Op::Gt | Op::Ge => set_once(before, TimeSpec::Ago(age), term),
+            _ => Err(term.op_error()),
+        };
+    }
+
+    let time = parse_instant(&term.value).ok_or_else(|| term.value_error("data inválida (use 7d, 2024-01-31 ou @timestamp)"))?;
+    // `>` e `<=` incluem o instante seguinte, que precisa existir
+    let next = || time.checked_add(1).map(TimeSpec::At).ok_or_else(|| term.value_error("data fora do intervalo"));
+    match op {
+        Op::Ge => set_once(after, TimeSpec::At(time), term),
+        Op::Gt => set_once(after, next()?, term),
+        Op::Lt => set_once(before, TimeSpec::At(time), term),
+        Op::Le => set_once(before, next()?, term),
+        _ => Err(term.op_error()),
+    }
+}
+
+// `1024`, `10KB`, `1.5GB`...
+fn parse_size(value: &str) -> Option<u64> {
+    let split = value.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let multiplier = match unit.to_uppercase().as_str() {
+        "" | "B" => 1,
+        "K" | "KB" => 1 << 10,
+        "M" | "MB" => 1 << 20,
+        "G" | "GB" => 1 << 30,
+        "T" | "TB" => 1 << 40,
+        _ => return None,
+    };
+
+    if let Ok(number) = number.parse::<u64>() {
+        return number.checked_mul(multiplier);
+    }
+    let number: f64 = number.parse().ok()?;
+    let size = (number * multiplier as f64).round();
+    (size.is_finite() && size >= 0.0 && size < u64::MAX as f64).then_some(size as u64)
+}
+
+// `644` (exato), `-4000` (todos os bits) ou `/022` (algum bit), em octal
+fn parse_mode(value: &str) -> Option<ModeFilter> {
+    let (constructor, digits): (fn(u32) -> ModeFilter, &str) = match value.as_bytes()[0] {
+        b'-' => (ModeFilter::All, &value[1..]),
+        b'/' => (ModeFilter::Any, &value[1..]),
+        _ => (ModeFilter::Exact, value),
+    };
+    if digits.is_empty() || !digits.bytes().all(|b| (b'0'..=b'7').contains(&b)) {
+        return None;
+    }
+    let bits = u32::from_str_radix(digits, 8).ok()?;
+    (bits <= 0o7777).then(|| constructor(bits))
+}
+
+fn format_mode(mode: &ModeFilter) -> String {
+    match mode {
+        ModeFilter::Exact(bits) => format!("{:03o}", bits),
+        ModeFilter::All(bits) => format!("-{:03o}", bits),
+        ModeFilter::Any(bits) => format!("/{:03o}", bits),
+    }
+}
+
+// `30s`, `15m`, `12h`, `7d`, `2w`
+fn parse_duration(value: &str) -> Option<Duration> {
+    let split = value.find(|c: char| !c.is_ascii_digit())?;
+    let (number, unit) = value.split_at(split);
+    let seconds = DURATION_UNITS.iter().find(|(name, _)| *name == unit)?.1;
+    number.parse::<u64>().ok()?.checked_mul(seconds).map(Duration::from_secs)
+}
+
+// `@1700000000` ou `2024-01-31` (meia-noite UTC)
+fn parse_instant(value: &str) -> Option<u64> {
+    if let Some(timestamp) = value.strip_prefix('@') {
+        return timestamp.parse().ok();
+    }
+
+    let mut parts = value.splitn(3, '-');
+    // Anos cabem em u32, o que mantém as contas de `days_from_civil` sem estouro
+    let year = i64::from(parts.next()?.parse::<u32>().ok()?);
+    let month: u32 = parts.next()?.parse().ok()?;
+    let day: u32 = parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+        return None;
+    }
+
+    let days = days_from_civil(year, month, day);
+    u64::try_from(days).ok()?.checked_mul(SECONDS_PER_DAY)
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// Dias desde 1970-01-01 no calendário gregoriano
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+// Inverso de `days_from_civil`
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+// ====================================================================
+// FORMATAÇÃO
+// ====================================================================
+
+fn format_size(size: u64) -> String {
+    SIZE_UNITS
+        .iter()
+        .find(|(_, unit)| size > 0 && size.is_multiple_of(*unit))
+        .map_or(size.to_string(), |(name, unit)| format!("{}{}", size / unit, name))
+}
+
+fn format_time(time: &TimeSpec) -> String {
+    match time {
+        TimeSpec::Ago(age) => {
+            let seconds = age.as_secs();
+            let (name, unit) = DURATION_UNITS
+                .iter()
+                .find(|(_, unit)| seconds.is_multiple_of(*unit))
+                .expect("a unidade de segundos divide qualquer valor");
+            format!("{}{}", seconds / unit, name)
+        }
+        TimeSpec::At(timestamp) if timestamp.is_multiple_of(SECONDS_PER_DAY) => {
+            let (year, month, day) = civil_from_days((timestamp / SECONDS_PER_DAY) as i64);
+            format!("{:04}-{:02}-{:02}", year, month, day)
+        }
+        TimeSpec::At(timestamp) => format!("@{}", timestamp),
+    }
+}
+
+// Coloca o valor entre aspas quando necessário para ser lido de volta
+fn quote(value: &str) -> String {
+    if !value.is_empty() && !value.starts_with('"') && !value.contains(char::is_whitespace) {
+        return value.to_string();
+    }
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn push_range(terms: &mut Vec<String>, key: &str, min: Option<String>, max: Option<String>) {
+    match (min, max) {
+        (Some(min), Some(max)) if min == max => terms.push(format!("{}={}", key, min)),
+        (min, max) => {
+            if let Some(min) = min {
+                terms.push(format!("{}>={}", key, min));
+            }
+            if let Some(max) = max {
+                terms.push(format!("{}<={}", key, max));
+            }
+        }
+    }
+}
+
+/// Escreve o filtro na linguagem de consulta de `FileFilter::parse`
+///
+/// Apenas os critérios e `recursive` são escritos; as demais opções de
+/// percurso (`threads`, `symlinks`, arquivos de exclusão...) não fazem parte
+/// da linguagem. Um `predicate` só é escrito se `Predicate::describe`
+/// retornar uma descrição: combinações com `or`, closures e negações sem
+/// termo equivalente na linguagem (como a de `Criterion::extensions`) são
+/// omitidas, e o texto fica mais amplo que o filtro.
+impl fmt::Display for FileFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut terms = Vec::new();
+
+        if self.recursive {
+            terms.push("recursive".to_string());
+        }
+        if !self.include_directories {
+            terms.push("type:file".to_string());
+        }
+        if !self.include_files {
+            terms.push("type:dir".to_string());
+        }
+        if !self.include_hidden {
+            terms.push("!hidden".to_string());
+        }
+
+        let extension_lists = [("ext", &self.extensions), ("!ext", &self.exclude_extensions)];
+        for (key, extensions) in extension_lists {
+            if let Some(extensions) = extensions {
+                terms.push(format!("{}:{}", key, quote(&extensions.join(","))));
+            }
+        }
+
+        let pattern_lists = [
+            ("name", &self.names),
+            ("!name", &self.exclude_names),
+            ("prune", &self.prune_directories),
+            ("mime", &self.mime_types),
+        ];
+        for (key, patterns) in pattern_lists {
+            if let Some(patterns) = patterns {
+                let patterns: Vec<&str> = patterns.iter().map(Pattern::as_str).collect();
+                terms.push(format!("{}:{}", key, quote(&patterns.join(","))));
+            }
+        }
+
+        let regexes = [
+            ("regex", &self.name_regex),
+            ("!regex", &self.exclude_name_regex),
+            ("path", &self.path_regex),
+            ("!path", &self.exclude_path_regex),
+        ];
+        for (key, regex) in regexes {
+            if let Some(regex) = regex {
+                terms.push(format!("{}:{}", key, quote(regex.as_str())));
+            }
+        }
+
+        push_range(&mut terms, "size", self.min_size.map(format_size), self.max_size.map(format_size));
+        push_range(&mut terms, "depth", self.min_depth.map(|d| d.to_string()), self.max_depth.map(|d| d.to_string()));
+
+        if let Some(mode) = &self.mode {
+            terms.push(format!("perm:{}", format_mode(mode)));
+        }
+        if let Some(uid) = self.uid {
+            terms.push(format!("uid={}", uid));
+        }
+        if let Some(gid) = self.gid {
+            terms.push(format!("gid={}", gid));
+        }
+
+        let times = [
+            ("modified", &self.modified_after, &self.modified_before),
+            ("accessed", &self.accessed_after, &self.accessed_before),
+            ("created", &self.created_after, &self.created_before),
+        ];
+        for (key, after, before) in times {
+            // Idades usam `<`/`>` ("há menos de"/"há mais de"); instantes, `>=`/`<`
+            match after {
+                Some(time @ TimeSpec::Ago(_)) => terms.push(format!("{}<{}", key, format_time(time))),
+                Some(time) => terms.push(format!("{}>={}", key, format_time(time))),
+                None => {}
+            }
+            match before {
+                Some(time @ TimeSpec::Ago(_)) => terms.push(format!("{}>{}", key, format_time(time))),
+                Some(time) => terms.push(format!("{}<{}", key, format_time(time))),
+                None => {}
+            }
+        }
+
+        if let Some(description) = self.predicate.as_ref().and_then(|predicate| predicate.describe()) {
+            terms.push(description);
+        }
+
+        f.write_str(&terms.join(" "))
+    }
+}
+
+// ====================================================================
+// TESTES UNITÁRIOS
+// ====================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::file_info;
+    use crate::Criterion;
+
+    fn parse_error(query: &str) -> (usize, String) {
+        match FileFilter::parse(query) {
+            Err(ArchivusError::ParseError { position, message }) => (position, message),
+            other => panic!("{:?} deveria falhar, mas retornou {:?}", query, other),
+        }
+    }
+
+    #[test]
+    fn test_query_parse() {
+        let filter = FileFilter::parse("ext:log,.txt size>10MB modified<7d !name:*.tmp recursive").unwrap();
+        assert_eq!(filter.extensions, Some(vec!["log".to_string(), "txt".to_string()]));
+        assert_eq!(filter.min_size, Some(10 * 1024 * 1024 + 1));
+        assert_eq!(filter.modified_after, Some(TimeSpec::days_ago(7)));
+        assert_eq!(filter.exclude_names.as_ref().map(Vec::len), Some(1));
+        assert!(filter.recursive);
+
+        let file = |name: &str| FileInfo {
+            modified: Some(crate::unix_seconds(Ok(std::time::SystemTime::now())).unwrap()),
+            ..file_info(name, 20 * 1024 * 1024)
+        };
+        assert!(filter.matches(&file("app.log")));
+        assert!(!filter.matches(&file("app.txt.gz")));
+
+        let not_tmp = FileFilter::parse("!name:*.tmp").unwrap();
+        assert!(!not_tmp.matches(&file("RASCUNHO.TMP")));
+        assert!(not_tmp.matches(&file("rascunho.txt")));
+
+        let filter = FileFilter::parse(r#"type:file size=0 depth>=2 depth<4 created>=2024-02-29 regex:"a b" !path:\.git/"#).unwrap();
+        assert!(!filter.include_directories);
+        assert_eq!((filter.min_size, filter.max_size), (Some(0), Some(0)));
+        assert_eq!((filter.min_depth, filter.max_depth), (Some(2), Some(3)));
+        assert_eq!(filter.created_after, Some(TimeSpec::At(1_709_164_800)));
+        assert_eq!(filter.name_regex.unwrap().as_str(), "a b");
+        assert_eq!(filter.exclude_path_regex.unwrap().as_str(), r"\.git/");
+
+        let filter = FileFilter::parse("perm:-4000 uid=0").unwrap();
+        assert_eq!(filter.mode, Some(ModeFilter::All(0o4000)));
+        assert_eq!((filter.uid, filter.gid), (Some(0), None));
+    }
+
+    #[test]
+    fn test_query_round_trip() {
+        let queries = [
+            "",
+            "recursive type:file ext:log,txt size>=1MB size<=1536",
+            r#"name:*.tmp,core regex:"^a b$" !path:\.git/ depth=1"#,
+            "modified<3d modified>2w accessed>=2024-01-31 created<@1700000001",
+            "ext:rs !ext:bak !size=0",
+            "recursive !ext:bak,tmp !name:*~ prune:.git,node_modules",
+            "mime:image/*,application/pdf !mime:image/gif",
+            "type:dir !hidden depth<=2",
+            "type:file perm:/022 uid=1000 gid=0 !perm:-4000",
+        ];
+        for query in queries {
+            let filter = FileFilter::parse(query).unwrap();
+            assert_eq!(filter.to_string(), query);
+            assert_eq!(query.parse::<FileFilter>().unwrap().to_string(), query);
+        }
+
+        assert_eq!(FileFilter::parse("size>10MB").unwrap().to_string(), "size>=10485761");
+
+        // Predicados são escritos quando a linguagem os expressa; os demais
+        // (OU, closures, negações sem termo equivalente) não são escritos
+        let with_predicate = |predicate: std::sync::Arc<dyn Predicate>| {
+            FileFilter { recursive: true, predicate: Some(predicate), ..Default::default() }.to_string()
+        };
+        let negated = with_predicate(std::sync::Arc::new(Criterion::max_size(5).not().and(Criterion::files())));
+        assert_eq!(negated, "recursive !size<=5 type:file");
+        assert_eq!(FileFilter::parse(&negated).unwrap().to_string(), "recursive type:file !size<=5");
+        assert_eq!(with_predicate(std::sync::Arc::new(Criterion::extensions(&["log"]).not())), "recursive");
+        assert_eq!(with_predicate(std::sync::Arc::new(Criterion::files().or(Criterion::min_size(1)))), "recursive");
+        assert_eq!(format_time(&TimeSpec::At(0)), "1970-01-01");
+        assert_eq!(parse_instant("2000-03-01"), Some(951_868_800));
+    }
+
+    #[test]
+    fn test_query_errors() {
+        assert_eq!(parse_error("ext:log tamanho>1").0, 8);
+        assert_eq!(parse_error("size>10XB").0, 5);
+        assert_eq!(parse_error("size:10").0, 4);
+        assert_eq!(parse_error("ext:log ext:txt").0, 8);
+        assert_eq!(parse_error("name:\"sem fim").0, 5);
+        assert_eq!(parse_error("modified=7d").0, 8);
+        assert_eq!(parse_error("created<2023-02-29").0, 8);
+        assert_eq!(parse_error("regex:(").0, 6);
+        assert_eq!(parse_error("ext:a,,b").0, 4);
+        assert_eq!(parse_error("!recursive").0, 0);
+        assert_eq!(parse_error("ext").0, 3);
+        assert_eq!(parse_error("size<0").0, 5);
+        assert_eq!(parse_error("hidden").0, 0);
+        assert_eq!(parse_error("perm:/9").0, 5);
+        assert_eq!(parse_error("perm:17777").0, 5);
+        assert_eq!(parse_error("uid>10").0, 3);
+        assert_eq!(parse_error("modified>@18446744073709551615").0, 9);
+        assert_eq!(parse_error("modified<=@18446744073709551615").0, 10);
+        assert_eq!(parse_error("modified<9223372036854775807-01-01").0, 9);
+        assert!(FileFilter::parse("modified>=@18446744073709551615").is_ok());
+
+        let (_, message) = parse_error("tamanho>1");
+        assert!(message.contains("tamanho"));
+        let error = FileFilter::parse("ext:log tamanho>1").unwrap_err();
+        assert_eq!(error.to_string(), "Erro de sintaxe na posição 8: campo desconhecido 'tamanho'");
+    }
+}
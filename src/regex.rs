@@ -0,0 +1,745 @@
+// ====================================================================
+// EXPRESSÕES REGULARES
+// ====================================================================
+// Motor de expressões regulares sem dependências: o padrão é compilado
+// para um programa executado por uma máquina de Pike, que avança todas
+// as alternativas em paralelo e nunca retrocede
+
+use std::fmt;
+use std::sync::Arc;
+
+use crate::ArchivusError;
+
+// Limite de instruções do programa compilado (repetições como `a{1000}`
+// são expandidas)
+const MAX_PROGRAM_SIZE: usize = 100_000;
+
+// Maior contador aceito em `{n,m}`
+const MAX_REPEAT: u32 = 1_000;
+
+// Classes abreviadas (`\d`, `\w`, `\s`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Perl {
+    Digit,
+    Word,
+    Space,
+}
+
+impl Perl {
+    fn matches(self, c: char) -> bool {
+        match self {
+            Perl::Digit => c.is_ascii_digit(),
+            Perl::Word => c.is_alphanumeric() || c == '_',
+            Perl::Space => c.is_whitespace(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum ClassItem {
+    Range(char, char),
+    Perl { class: Perl, negated: bool },
+}
+
+// Conjunto de caracteres (`[...]`, `\d`, `\W`...)
+#[derive(Debug, Clone, PartialEq)]
+struct Class {
+    negated: bool,
+    items: Vec<ClassItem>,
+}
+
+impl Class {
+    fn perl(class: Perl, negated: bool) -> Self {
+        Self {
+            negated: false,
+            items: vec![ClassItem::Perl { class, negated }],
+        }
+    }
+
+    fn matches(&self, c: char) -> bool {
+        let found = self.items.iter().any(|item| match *item {
+            ClassItem::Range(lo, hi) => lo <= c && c <= hi,
+            ClassItem::Perl { class, negated } => class.matches(c) != negated,
+        });
+        found != self.negated
+    }
+}
+
+// Asserções de largura zero
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Look {
+    /// `^`
+    Start,
+    /// `$`
+    End,
+    /// `\b`
+    WordBoundary,
+    /// `\B`
+    NotWordBoundary,
+}
+
+impl Look {
+    fn holds(self, prev: Option<char>, next: Option<char>) -> bool {
+        let is_word = |c: Option<char>| c.is_some_and(|c| Perl::Word.matches(c));
+        match self {
+            Look::Start => prev.is_none(),
+            Look::End => next.is_none(),
+            Look::WordBoundary => is_word(prev) != is_word(next),
+            Look::NotWordBoundary => is_word(prev) == is_word(next),
+        }
+    }
+}
+
+// Árvore sintática do padrão
+#[derive(Debug, Clone)]
+enum Node {
+    Empty,
+    Literal(char),
+    /// `.`: qualquer caractere exceto quebra de linha
+    Any,
+    Class(Class),
+    Look(Look),
+    Concat(Vec<Node>),
+    Alternate(Vec<Node>),
+    Repeat {
+        node: Box<Node>,
+        min: u32,
+        max: Option<u32>,
+        greedy: bool,
+    },
+}
+
+// ====================================================================
+// ANÁLISE DO PADRÃO
+// ====================================================================
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn error(&self, message: &str) -> ArchivusError {
+        ArchivusError::InvalidPattern(format!("{} (posição {})", message, self.pos))
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse_alternation(&mut self) -> Result<Node, ArchivusError> {
+        let mut alternatives = vec![self.parse_concat()?];
+        while self.eat('|') {
+            alternatives.push(self.parse_concat()?);
+        }
+
+        Ok(if alternatives.len() == 1 { alternatives.pop().unwrap() } else { Node::Alternate(alternatives) })
+    }
+
+    fn parse_concat(&mut self) -> Result<Node, ArchivusError> {
+        let mut nodes = Vec::new();
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            let atom = self.parse_atom()?;
+            nodes.push(self.parse_quantifier(atom)?);
+        }
+
+        Ok(match nodes.len() {
+            0 => Node::Empty,
+            1 => nodes.pop().unwrap(),
+            _ => Node::Concat(nodes),
+        })
+    }
+
+    fn parse_quantifier(&mut self, atom: Node) -> Result<Node, ArchivusError> {
+        let start = self.pos;
+        let (min, max) = match self.peek() {
+            Some('*') => (0, None),
+            Some('+') => (1, None),
+            Some('?') => (0, Some(1)),
+            Some('{') => match self.parse_counts() {
+                Some(counts) => counts,
+                None => {
+                    // `{` que não forma uma repetição é literal
+                    self.pos = start;
+                    return Ok(atom);
+                }
+            },
+            _ => return Ok(atom),
+        };
+        if self.pos == start {
+            self.pos += 1;
+        }
+
+        if matches!(atom, Node::Look(_) | Node::Empty) {
+            return Err(self.error("nada para repetir"));
+        }
+        if max.is_some_and(|max| max < min) {
+            return Err(self.error("repetição com mínimo maior que o máximo"));
+        }
+        if min > MAX_REPEAT || max.is_some_and(|max| max > MAX_REPEAT) {
+            return Err(self.error("repetição grande demais"));
+        }
+
+        let greedy = !self.eat('?');
+        if matches!(self.peek(), Some('*' | '+' | '?')) {
+            return Err(self.error("quantificador repetido"));
+        }
+
+        Ok(Node::Repeat {
+            node: Box::new(atom),
+            min,
+            max,
+            greedy,
+        })
+    }
+
+    // `{n}`, `{n,}` ou `{n,m}`; retorna None se o texto não tem esse formato
+    fn parse_counts(&mut self) -> Option<(u32, Option<u32>)> {
+        self.pos += 1;
+        let min = self.parse_number()?;
+        let max = if self.eat(',') {
+            if self.peek() == Some('}') { None } else { Some(self.parse_number()?) }
+        } else {
+            Some(min)
+        };
+        self.eat('}').then_some((min, max))
+    }
+
+    fn parse_number(&mut self) -> Option<u32> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        let digits: String = self.chars[start..self.pos].iter().collect();
+        // Números enormes viram u32::MAX e são rejeitados como repetição grande demais
+        (!digits.is_empty()).then(|| digits.parse().unwrap_or(u32::MAX))
+    }
+
+    fn parse_atom(&mut self) -> Result<Node, ArchivusError> {
+        let c = self.peek().ok_or_else(|| self.error("fim inesperado"))?;
+        self.pos += 1;
+
+        match c {
+            '(' => {
+                // `(?:...)` agrupa sem outro efeito, como `(...)`
+                if self.eat('?') && !self.eat(':') {
+                    return Err(self.error("grupo especial não suportado"));
+                }
+                let node = self.parse_alternation()?;
+                if !self.eat(')') {
+                    return Err(self.error("parêntese não fechado"));
+                }
+                Ok(node)
+            }
+            ')' => Err(self.error("parêntese sem abertura")),
+            '*' | '+' | '?' => {
+                self.pos -= 1;
+                Err(self.error("nada para repetir"))
+            }
+            '[' => self.parse_class().map(Node::Class),
+            '.' => Ok(Node::Any),
+            '^' => Ok(Node::Look(Look::Start)),
+            '$' => Ok(Node::Look(Look::End)),
+            '\\' => self.parse_escape(),
+            c => Ok(Node::Literal(c)),
+        }
+    }
+
+    fn parse_escape(&mut self) -> Result<Node, ArchivusError> {
+        let c = self.peek().ok_or_else(|| self.error("escape incompleto"))?;
+        self.pos += 1;
+
+        Ok(match c {
+            'b' => Node::Look(Look::WordBoundary),
+            'B' => Node::Look(Look::NotWordBoundary),
+            _ => match self.escaped_class(c) {
+                Some(class) => Node::Class(class),
+                None => Node::Literal(self.escaped_literal(c)?),
+            },
+        })
+    }
+
+    fn escaped_class(&self, c: char) -> Option<Class> {
+        let class = match c.to_ascii_lowercase() {
+            'd' => Perl::Digit,
+            'w' => Perl::Word,
+            's' => Perl::Space,
+            _ => return None,
+        };
+        Some(Class::perl(class, c.is_ascii_uppercase()))
+    }
+
+    fn escaped_literal(&self, c: char) -> Result<char, ArchivusError> {
+        match c {
+            't' => Ok('\t'),
+            'n' => Ok('\n'),
+            'r' => Ok('\r'),
+            c if c.is_ascii_alphanumeric() => Err(self.error(&format!("escape desconhecido: \\{}", c))),
+            c => Ok(c),
+        }
+    }
+
+    fn parse_class(&mut self) -> Result<Class, ArchivusError> {
+        let negated = self.eat('^');
+        let mut items = Vec::new();
+        let mut first = true;
+
+        loop {
+            let c = self.peek().ok_or_else(|| self.error("classe não fechada"))?;
+            self.pos += 1;
+
+            // `]` logo após a abertura é literal
+            if c == ']' && !first {
+                break;
+            }
+            first = false;
+
+            let lo = if c == '\\' {
+                let escaped = self.peek().ok_or_else(|| self.error("escape incompleto"))?;
+                self.pos += 1;
+                if let Some(class) = self.escaped_class(escaped) {
+                    items.extend(class.items);
+                    continue;
+                }
+                self.escaped_literal(escaped)?
+            } else {
+                c
+            };
+
+            // `-` no final da classe é literal
+            if self.peek() == Some('-') && self.chars.get(self.pos + 1).is_some_and(|&next| next != ']') {
+                self.pos += 1;
+                let mut hi = self.peek().ok_or_else(|| self.error("classe não fechada"))?;
+                self.pos += 1;
+                if hi == '\\' {
+                    let escaped = self.peek().ok_or_else(|| self.error("escape incompleto"))?;
+                    self.pos += 1;
+                    hi = self.escaped_literal(escaped)?;
+                }
+                if hi < lo {
+                    return Err(self.error("intervalo invertido na classe"));
+                }
+                items.push(ClassItem::Range(lo, hi));
+            } else {
+                items.push(ClassItem::Range(lo, lo));
+            }
+        }
+
+        Ok(Class { negated, items })
+    }
+}
+
+// ====================================================================
+// COMPILAÇÃO
+// ====================================================================
+
+#[derive(Debug, Clone)]
+enum Inst {
+    Char(char),
+    Any,
+    Class(Class),
+    Look(Look),
+    /// Continua nos dois destinos; o primeiro tem prioridade
+    Split(usize, usize),
+    Jump(usize),
+    Match,
+}
+
+#[derive(Default)]
+struct Compiler {
+    insts: Vec<Inst>,
+    ignore_case: bool,
+}
+
+impl Compiler {
+    fn push(&mut self, inst: Inst) -> Result<usize, ArchivusError> {
+        if self.insts.len() >= MAX_PROGRAM_SIZE {
+            return Err(ArchivusError::InvalidPattern("expressão regular grande demais".to_string()));
+        }
+        self.insts.push(inst);
+        Ok(self.insts.len() - 1)
+    }
+
+    fn emit(&mut self, node: &Node) -> Result<(), ArchivusError> {
+        match node {
+            Node::Empty => {}
+            Node::Literal(c) => {
+                let c = if self.ignore_case { fold_case(*c) } else { *c };
+                self.push(Inst::Char(c))?;
+            }
+            Node::Any => {
+                self.push(Inst::Any)?;
+            }
+            Node::Class(class) => {
+                self.push(Inst::Class(class.clone()))?;
+            }
+            Node::Look(look) => {
+                self.push(Inst::Look(*look))?;
+            }
+            Node::Concat(nodes) => {
+                for node in nodes {
+                    self.emit(node)?;
+                }
+            }
+            Node::Alternate(alternatives) => {
+                let mut jumps = Vec::new();
+                let (last, rest) = alternatives.split_last().expect("alternância sem alternativas");
+                for alternative in rest {
+                    let split = self.push(Inst::Split(0, 0))?;
+                    self.emit(alternative)?;
+                    jumps.push(self.push(Inst::Jump(0))?);
+                    self.insts[split] = Inst::Split(split + 1, self.insts.len());
+                }
+                self.emit(last)?;
+
+                let end = self.insts.len();
+                for jump in jumps {
+                    self.insts[jump] = Inst::Jump(end);
+                }
+            }
+            Node::Repeat { node, min, max, greedy } => {
+                for _ in 0..*min {
+                    self.emit(node)?;
+                }
+
+                match max {
+                    None => {
+                        let split = self.push(Inst::Split(0, 0))?;
+                        self.emit(node)?;
+                        self.push(Inst::Jump(split))?;
+                        self.insts[split] = self.split(split + 1, self.insts.len(), *greedy);
+                    }
+                    Some(max) => {
+                        let mut splits = Vec::new();
+                        for _ in *min..*max {
+                            splits.push(self.push(Inst::Split(0, 0))?);
+                            self.emit(node)?;
+                        }
+                        let end = self.insts.len();
+                        for split in splits {
+                            self.insts[split] = self.split(split + 1, end, *greedy);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn split(&self, body: usize, out: usize, greedy: bool) -> Inst {
+        if greedy { Inst::Split(body, out) } else { Inst::Split(out, body) }
+    }
+}
+
+// Simplificação de maiúsculas e minúsculas usada na comparação sem distinção
+fn fold_case(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+// ====================================================================
+// EXECUÇÃO (MÁQUINA DE PIKE)
+// ====================================================================
+
+// Conjunto de threads ativas, sem repetição de instrução, na ordem de prioridade
+struct Threads {
+    // (instrução, início da correspondência)
+    dense: Vec<(usize, usize)>,
+    sparse: Vec<usize>,
+}
+
+impl Threads {
+    fn new(size: usize) -> Self {
+        Self {
+            dense: Vec::with_capacity(size),
+            sparse: vec![0; size],
+        }
+    }
+
+    fn contains(&self, pc: usize) -> bool {
+        self.dense.get(self.sparse[pc]).is_some_and(|&(dense_pc, _)| dense_pc == pc)
+    }
+
+    fn insert(&mut self, pc: usize, start: usize) {
+        self.sparse[pc] = self.dense.len();
+        self.dense.push((pc, start));
+    }
+}
+
+#[derive(Debug)]
+struct Program {
+    insts: Vec<Inst>,
+    ignore_case: bool,
+}
+
+impl Program {
+    fn char_matches(&self, inst: &Inst, c: char) -> bool {
+        match inst {
+            Inst::Char(expected) => {
+                let c = if self.ignore_case { fold_case(c) } else { c };
+                *expected == c
+            }
+            Inst::Any => c != '\n',
+            Inst::Class(class) => {
+                class.matches(c)
+                    || (self.ignore_case
+                        && (class.matches(fold_case(c)) || c.to_uppercase().next().is_some_and(|upper| class.matches(upper))))
+            }
+            _ => false,
+        }
+    }
+
+    // Adiciona a thread e todas as que ela alcança sem consumir caracteres,
+    // em ordem de prioridade
+    fn add_thread(&self, threads: &mut Threads, stack: &mut Vec<usize>, pc: usize, start: usize, prev: Option<char>, next: Option<char>) {
+        stack.push(pc);
+        while let Some(pc) = stack.pop() {
+            if threads.contains(pc) {
+                continue;
+            }
+            threads.insert(pc, start);
+
+            match self.insts[pc] {
+                Inst::Split(first, second) => {
+                    stack.push(second);
+                    stack.push(first);
+                }
+                Inst::Jump(target) => stack.push(target),
+                Inst::Look(look) if look.holds(prev, next) => stack.push(pc + 1),
+                _ => {}
+            }
+        }
+    }
+
+    // Correspondência mais à esquerda; entre as que começam na mesma posição,
+    // a preferida pelos quantificadores (gulosos ou não)
+    fn find(&self, text: &str) -> Option<(usize, usize)> {
+        let mut current = Threads::new(self.insts.len());
+        let mut next_threads = Threads::new(self.insts.len());
+        let mut stack = Vec::new();
+        let mut matched = None;
+        let mut prev = None;
+        let mut pos = 0;
+
+        loop {
+            let cur = text[pos..].chars().next();
+            if matched.is_none() {
+                self.add_thread(&mut current, &mut stack, 0, pos, prev, cur);
+            }
+            if current.dense.is_empty() {
+                break;
+            }
+
+            let next_pos = pos + cur.map_or(0, char::len_utf8);
+            let after = text[next_pos..].chars().next();
+            for i in 0..current.dense.len() {
+                let (pc, start) = current.dense[i];
+                let inst = &self.insts[pc];
+                if let Inst::Match = inst {
+                    matched = Some((start, pos));
+                    // Threads de prioridade menor são descartadas
+                    break;
+                }
+                if let Some(c) = cur
+                    && self.char_matches(inst, c)
+                {
+                    self.add_thread(&mut next_threads, &mut stack, pc + 1, start, cur, after);
+                }
+            }
+
+            if cur.is_none() {
+                break;
+            }
+            prev = cur;
+            pos = next_pos;
+            std::mem::swap(&mut current, &mut next_threads);
+            next_threads.dense.clear();
+        }
+
+        matched
+    }
+}
+
+/// Expressão regular compilada, sem dependências externas
+///
+/// Sintaxe suportada:
+/// - `.`: qualquer caractere exceto quebra de linha
+/// - `[abc]`, `[a-z]`, `[^x]`: classes de caracteres
+/// - `\d` (dígitos ASCII), `\w` (letras, dígitos e `_`), `\s` (espaços) e as
+///   negações `\D`, `\W`, `\S`, também dentro de classes
+/// - `^` e `$`: início e fim do texto; `\b` e `\B`: limites de palavra
+/// - `*`, `+`, `?`, `{n}`, `{n,}`, `{n,m}`, com `?` no final para a versão
+///   não gulosa (`*?`)
+/// - `a|b`, `(...)` e `(?:...)`
+/// - `\` escapa metacaracteres; `\t`, `\n` e `\r`
+///
+/// A busca é feita com uma máquina de Pike: o tempo é proporcional ao tamanho
+/// do texto vezes o tamanho do padrão, sem retrocesso exponencial. Como em
+/// outras bibliotecas, a expressão pode corresponder a qualquer parte do
+/// texto; use `^` e `$` para exigir o texto inteiro.
+///
+/// # Exemplos
+/// ```rust
+/// use archivus::Regex;
+///
+/// let regex = Regex::new(r"^report_\d{4}-\d{2}\.csv$")?;
+///
+/// assert!(regex.is_match("report_2024-03.csv"));
+/// assert!(!regex.is_match("report_24-03.csv"));
+/// assert_eq!(Regex::new(r"\d+")?.find("abc 123 def"), Some((4, 7)));
+/// # Ok::<(), archivus::ArchivusError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Regex {
+    pattern: String,
+    program: Arc<Program>,
+}
+
+impl Regex {
+    /// Compila uma expressão regular
+    pub fn new(pattern: &str) -> Result<Self, ArchivusError> {
+        Self::compile(pattern, false)
+    }
+
+    /// Compila uma expressão regular que ignora maiúsculas e minúsculas
+    pub fn new_ignore_case(pattern: &str) -> Result<Self, ArchivusError> {
+        Self::compile(pattern, true)
+    }
+
+    fn compile(pattern: &str, ignore_case: bool) -> Result<Self, ArchivusError> {
+        let mut parser = Parser {
+            chars: pattern.chars().collect(),
+            pos: 0,
+        };
+        let node = parser.parse_alternation()?;
+        if parser.peek().is_some() {
+            return Err(parser.error("parêntese sem abertura"));
+        }
+
+        let mut compiler = Compiler {
+            ignore_case,
+            ..Default::default()
+        };
+        compiler.emit(&node)?;
+        compiler.push(Inst::Match)?;
+
+        Ok(Self {
+            pattern: pattern.to_string(),
+            program: Arc::new(Program {
+                insts: compiler.insts,
+                ignore_case,
+            }),
+        })
+    }
+
+    /// Escapa os caracteres especiais de um texto, para que a expressão
+    /// resultante corresponda apenas ao próprio texto
+    ///
+    /// # Exemplos
+    /// ```rust
+    /// use archivus::Regex;
+    ///
+    /// let regex = Regex::new(&Regex::escape("a.b*"))?;
+    /// assert!(regex.is_match("xa.b*y"));
+    /// assert!(!regex.is_match("aXbbb"));
+    /// # Ok::<(), archivus::ArchivusError>(())
+    /// ```
+    pub fn escape(text: &str) -> String {
+        let mut escaped = String::with_capacity(text.len());
+        for c in text.chars() {
+            if c.is_ascii_punctuation() {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+        }
+        escaped
+    }
+
+    /// Retorna o padrão original
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Verifica se a expressão corresponde a alguma parte do texto
+    pub fn is_match(&self, text: &str) -> bool {
+        self.find(text).is_some()
+    }
+
+    /// Retorna o intervalo em bytes da primeira correspondência no texto
+    pub fn find(&self, text: &str) -> Option<(usize, usize)> {
+        self.program.find(text)
+    }
+}
+
+impl fmt::Display for Regex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.pattern)
+    }
+}
+
+// ====================================================================
+// TESTES UNITÁRIOS
+// ====================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(pattern: &str, text: &str) -> Option<(usize, usize)> {
+        Regex::new(pattern).unwrap().find(text)
+    }
+
+    #[test]
+    fn test_regex_matching() {
+        assert!(Regex::new(r"^report_\d{4}-\d{2}\.csv$").unwrap().is_match("report_2024-03.csv"));
+        assert!(!Regex::new(r"^report_\d{4}-\d{2}\.csv$").unwrap().is_match("report_2024-3.csv"));
+
+        assert_eq!(find("b+", "abbbc"), Some((1, 4)));
+        assert_eq!(find("b+?", "abbbc"), Some((1, 2)));
+        assert_eq!(find("a|ab", "ab"), Some((0, 1)));
+        assert_eq!(find("(a|ab)(c|bcd)", "abcd"), Some((0, 4)));
+        assert_eq!(find("x*", "abc"), Some((0, 0)));
+        assert_eq!(find("c$", "abc"), Some((2, 3)));
+        assert_eq!(find("^b", "abc"), None);
+        assert_eq!(find(r"\bdef\b", "abc def"), Some((4, 7)));
+        assert_eq!(find(r"\bdef\b", "abcdef"), None);
+        assert_eq!(find("[^a-c]+", "abcxyz"), Some((3, 6)));
+        assert_eq!(find("[]a]+", "x]a]"), Some((1, 4)));
+        assert_eq!(find(r"[\d.-]+", "v1.2-3"), Some((1, 6)));
+        assert_eq!(find("a{2,3}", "aaaa"), Some((0, 3)));
+        assert_eq!(find("a{2,}", "aaaa"), Some((0, 4)));
+        assert_eq!(find("a{,2}", "a{,2}"), Some((0, 5)));
+        assert_eq!(find("ção", "ação"), Some((1, 6)));
+        assert_eq!(find(".", "\n"), None);
+
+        let regex = Regex::new_ignore_case("^RELATÓRIO[a-z]*$").unwrap();
+        assert!(regex.is_match("relatórioAnual"));
+    }
+
+    #[test]
+    fn test_regex_errors_and_limits() {
+        for pattern in ["(a", "a)", "*a", "a**", "[a-", "[z-a]", r"\q", "a{3,2}", "(?=a)", "a{5000}"] {
+            assert!(
+                matches!(Regex::new(pattern), Err(ArchivusError::InvalidPattern(_))),
+                "{:?} deveria ser inválido",
+                pattern
+            );
+        }
+        assert!(matches!(Regex::new("(a{1000}){1000}"), Err(ArchivusError::InvalidPattern(_))));
+
+        // Padrão que causa retrocesso exponencial em motores com backtracking
+        let text = "a".repeat(5_000);
+        assert!(!Regex::new("^(a+)+b$").unwrap().is_match(&text));
+    }
+}
@@ -0,0 +1,264 @@
+// ====================================================================
+// BUSCA POR CONTEÚDO
+// ====================================================================
+// Busca linha a linha no conteúdo dos arquivos, como o `grep`, com
+// padrões literais, wildcards ou expressões regulares
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use crate::mime::SNIFF_LEN;
+use crate::{ArchivusError, FileFilter, Regex};
+
+/// Padrão procurado em cada linha por `Archivus::search_content`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SearchPattern {
+    /// Texto exato
+    Literal(String),
+    /// Texto com `*` (qualquer sequência) e `?` (um caractere)
+    Wildcard(String),
+    /// Expressão regular (veja `Regex`); `^` e `$` se referem à linha
+    Regex(String),
+}
+
+impl SearchPattern {
+    // Todos os tipos de padrão são convertidos em uma expressão regular
+    fn compile(&self, ignore_case: bool) -> Result<Regex, ArchivusError> {
+        let pattern = match self {
+            SearchPattern::Literal(text) => Regex::escape(text),
+            SearchPattern::Wildcard(text) => text
+                .chars()
+                .map(|c| match c {
+                    '*' => ".*".to_string(),
+                    '?' => ".".to_string(),
+                    c => Regex::escape(&c.to_string()),
+                })
+                .collect(),
+            SearchPattern::Regex(pattern) => pattern.clone(),
+        };
+
+        if ignore_case { Regex::new_ignore_case(&pattern) } else { Regex::new(&pattern) }
+    }
+}
+
+/// Opções de `Archivus::search_content`
+///
+/// # Exemplos
+/// ```rust
+/// use archivus::{FileFilter, SearchOptions};
+///
+/// // Busca recursiva em arquivos .rs, com duas linhas de contexto
+/// let options = SearchOptions {
+///     ignore_case: true,
+///     context_before: 2,
+///     context_after: 2,
+///     filter: FileFilter {
+///         recursive: true,
+///         extensions: Some(vec!["rs".to_string()]),
+///         ..Default::default()
+///     },
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone)]
+pub struct SearchOptions {
+    /// Ignora maiúsculas e minúsculas
+    pub ignore_case: bool,
+    /// Linhas de contexto antes de cada correspondência
+    pub context_before: usize,
+    /// Linhas de contexto depois de cada correspondência
+    pub context_after: usize,
+    /// Ignora arquivos binários (com byte nulo nos primeiros 8 KB)
+    pub skip_binary: bool,
+    /// Número máximo de correspondências no total
+    pub max_matches: Option<usize>,
+    /// Número máximo de correspondências em cada arquivo
+    pub max_matches_per_file: Option<usize>,
+    /// Arquivos pesquisados; diretórios nunca são pesquisados
+    pub filter: FileFilter,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            ignore_case: false,
+            context_before: 0,
+            context_after: 0,
+            skip_binary: true,
+            max_matches: None,
+            max_matches_per_file: None,
+            filter: FileFilter::default(),
+        }
+    }
+}
+
+/// Linha que contém o padrão, encontrada por `Archivus::search_content`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentMatch {
+    /// Arquivo, como produzido pela busca
+    pub path: PathBuf,
+    /// Número da linha, a partir de 1
+    pub line_number: usize,
+    /// Posição do início da correspondência no arquivo, em bytes, mesmo
+    /// quando a linha não é UTF-8 válido
+    pub byte_offset: u64,
+    /// Linha, sem a quebra de linha; bytes inválidos em UTF-8 são trocados
+    /// por U+FFFD
+    pub line: String,
+    /// Intervalo da correspondência em `line` (o texto convertido), em bytes
+    pub range: (usize, usize),
+    /// Linhas anteriores, da mais distante para a mais próxima
+    pub context_before: Vec<String>,
+    /// Linhas seguintes
+    pub context_after: Vec<String>,
+}
+
+// Estado de uma busca em andamento, compartilhado entre os arquivos
+pub(crate) struct Searcher<'a> {
+    regex: Regex,
+    options: &'a SearchOptions,
+    pub(crate) matches: Vec<ContentMatch>,
+}
+
+impl<'a> Searcher<'a> {
+    pub(crate) fn new(pattern: &SearchPattern, options: &'a SearchOptions) -> Result<Self, ArchivusError> {
+        Ok(Self {
+            regex: pattern.compile(options.ignore_case)?,
+            options,
+            matches: Vec::new(),
+        })
+    }
+
+    /// Limite total atingido
+    pub(crate) fn is_done(&self) -> bool {
+        self.options.max_matches.is_some_and(|max| self.matches.len() >= max)
+    }
+
+    pub(crate) fn search_file(&mut self, path: &Path) -> Result<(), ArchivusError> {
+        let mut reader = BufReader::new(File::open(path)?);
+        if self.options.skip_binary && reader.fill_buf()?.iter().take(SNIFF_LEN).any(|&b| b == 0) {
+            return Ok(());
+        }
+
+        let first_match = self.matches.len();
+        let mut before: VecDeque<String> = VecDeque::with_capacity(self.options.context_before);
+        // Correspondências deste arquivo que ainda aguardam contexto posterior
+        let mut pending = first_match;
+        let mut found = 0;
+        let mut offset = 0u64;
+        let mut buffer = Vec::new();
+
+        for line_number in 1.. {
+            buffer.clear();
+            let read = reader.read_until(b'\n', &mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            let line_start = offset;
+            offset += read as u64;
+
+            let content = buffer.strip_suffix(b"\n").unwrap_or(&buffer);
+            let content = content.strip_suffix(b"\r").unwrap_or(content);
+            let line = String::from_utf8_lossy(content).into_owned();
+
+            // Esta linha é contexto posterior das correspondências anteriores
+            while pending < self.matches.len() && self.matches[pending].context_after.len() >= self.options.context_after {
+                pending += 1;
+            }
+            for previous in &mut self.matches[pending..] {
+                previous.context_after.push(line.clone());
+            }
+
+            let limit_reached = self.is_done() || self.options.max_matches_per_file.is_some_and(|max| found >= max);
+            if limit_reached {
+                // Continua apenas para completar o contexto posterior
+                if self.matches[pending..].iter().all(|m| m.context_after.len() >= self.options.context_after) {
+                    break;
+                }
+                continue;
+            }
+
+            if let Some((start, end)) = self.regex.find(&line) {
+                found += 1;
+                self.matches.push(ContentMatch {
+                    path: path.to_path_buf(),
+                    line_number,
+                    byte_offset: line_start + original_offset(content, start) as u64,
+                    line: line.clone(),
+                    range: (start, end),
+                    context_before: before.iter().cloned().collect(),
+                    context_after: Vec::new(),
+                });
+            }
+
+            if self.options.context_before > 0 {
+                if before.len() == self.options.context_before {
+                    before.pop_front();
+                }
+                before.push_back(line);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Converte uma posição no texto de `String::from_utf8_lossy(bytes)` na
+// posição correspondente em `bytes`. Cada sequência inválida vira um único
+// U+FFFD (3 bytes); uma posição dentro dele aponta para o início da sequência
+fn original_offset(bytes: &[u8], lossy: usize) -> usize {
+    let (mut converted, mut original) = (0, 0);
+    for chunk in bytes.utf8_chunks() {
+        let valid = chunk.valid().len();
+        if lossy < converted + valid {
+            return original + lossy - converted;
+        }
+        converted += valid;
+        original += valid;
+
+        if !chunk.invalid().is_empty() {
+            if lossy < converted + char::REPLACEMENT_CHARACTER.len_utf8() {
+                return original;
+            }
+            converted += char::REPLACEMENT_CHARACTER.len_utf8();
+            original += chunk.invalid().len();
+        }
+    }
+    original + lossy.saturating_sub(converted)
+}
+
+// ====================================================================
+// TESTES UNITÁRIOS
+// ====================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_pattern_compile() {
+        let literal = SearchPattern::Literal("a.b(c)".to_string()).compile(false).unwrap();
+        assert_eq!(literal.find("xx a.b(c)"), Some((3, 9)));
+        assert!(!literal.is_match("aXb(c)"));
+
+        let wildcard = SearchPattern::Wildcard("to?o*fix".to_string()).compile(true).unwrap();
+        assert_eq!(wildcard.find("// TODO: fix me"), Some((3, 12)));
+
+        let regex = SearchPattern::Regex(r"^fn \w+".to_string()).compile(false).unwrap();
+        assert!(regex.is_match("fn main() {"));
+        assert!(!regex.is_match("pub fn main() {"));
+        assert!(SearchPattern::Regex("(".to_string()).compile(false).is_err());
+    }
+
+    #[test]
+    fn test_original_offset() {
+        assert_eq!(original_offset(b"abc", 2), 2);
+        // "a\xff\xfeb" vira "a\u{FFFD}\u{FFFD}b": cada byte inválido é uma sequência
+        assert_eq!(original_offset(b"a\xff\xfeb", 7), 3);
+        assert_eq!(original_offset(b"a\xff\xfeb", 4), 2);
+        assert_eq!(original_offset(b"\xe2\x82b", 3), 2);
+        assert_eq!(original_offset(b"ab", 2), 2);
+    }
+}
@@ -0,0 +1,584 @@
+// ====================================================================
+// SNAPSHOTS DE DIRETÓRIOS
+// ====================================================================
+// Registro do estado de uma árvore (caminhos, tamanhos, datas, modos e
+// hashes opcionais) em um formato binário compacto e versionado, com
+// exportação para JSON
+//
+// Formato binário (inteiros em LEB128; `i64` em zigzag):
+//
+//   "ARCHSNAP" + versão (u16 little-endian)
+//   criação: segundos (i64), nanossegundos
+//   raiz: texto (tamanho + UTF-8)
+//   algoritmo: u8 (0 = sem hashes, 1 = sha256, 2 = blake2b, 3 = crc32, 4 = xxh64)
+//   quantidade de entradas
+//   cada entrada:
+//     bytes em comum com o caminho anterior, resto do caminho (texto)
+//     tipo (u8), flags (u8), tamanho
+//     [modificação: segundos (i64), nanossegundos]   flag 0x01
+//     [modo]                                          flag 0x02
+//     [destino do link (texto)]                       flag 0x04
+//     [hash, com o tamanho do algoritmo]              flag 0x08
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::{hash, relative_path, ArchivusError, Digest, FileInfo, FileType, HashAlgorithm, Timestamp};
+
+const MAGIC: &[u8; 8] = b"ARCHSNAP";
+
+const HAS_MODIFIED: u8 = 0x01;
+const HAS_MODE: u8 = 0x02;
+const HAS_LINK_TARGET: u8 = 0x04;
+const HAS_DIGEST: u8 = 0x08;
+
+// Tipos na ordem dos códigos do formato binário, com o nome usado no JSON
+const FILE_TYPES: [(FileType, &str); 8] = [
+    (FileType::Regular, "file"),
+    (FileType::Directory, "directory"),
+    (FileType::Symlink, "symlink"),
+    (FileType::Fifo, "fifo"),
+    (FileType::Socket, "socket"),
+    (FileType::BlockDevice, "block_device"),
+    (FileType::CharDevice, "char_device"),
+    (FileType::Unknown, "unknown"),
+];
+
+/// Item registrado em um `Snapshot`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotEntry {
+    /// Caminho relativo à raiz, separado por `/`
+    pub path: String,
+    /// Tipo do item
+    pub file_type: FileType,
+    /// Tamanho em bytes
+    pub size: u64,
+    /// Última modificação, com nanossegundos quando disponíveis
+    pub modified: Option<Timestamp>,
+    /// Bits de permissão (`0o7777`); `None` fora de sistemas Unix
+    pub mode: Option<u32>,
+    /// Destino do link simbólico, como gravado no link
+    pub link_target: Option<String>,
+    /// Hash do conteúdo, apenas para arquivos comuns
+    pub digest: Option<Digest>,
+}
+
+/// Estado de uma árvore em um momento
+///
+/// As entradas ficam em ordem de caminho (byte a byte). O snapshot pode ser
+/// gravado em um formato binário compacto (`save`, `to_bytes`) e lido de
+/// volta (`load`, `from_bytes`), ou exportado para JSON (`to_json`).
+///
+/// # Exemplos
+/// ```rust,no_run
+/// # fn main() -> Result<(), archivus::ArchivusError> {
+/// use archivus::{Archivus, FileFilter, HashAlgorithm, Snapshot};
+///
+/// let utils = Archivus::new();
+/// let filter = FileFilter { recursive: true, ..Default::default() };
+/// let snapshot = utils.snapshot("/srv/app", &filter, Some(HashAlgorithm::XxHash64))?;
+/// snapshot.save("antes.snap")?;
+///
+/// let anterior = Snapshot::load("antes.snap")?;
+/// println!("{} itens em {}", anterior.entries.len(), anterior.root.display());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    /// Diretório registrado
+    pub root: PathBuf,
+    /// Momento da captura
+    pub created: Timestamp,
+    /// Algoritmo dos hashes, se foram calculados
+    pub algorithm: Option<HashAlgorithm>,
+    /// Itens, em ordem de caminho
+    pub entries: Vec<SnapshotEntry>,
+}
+
+impl Snapshot {
+    /// Versão do formato binário gravada por `to_bytes`
+    pub const VERSION: u16 = 1;
+
+    /// Cria um snapshot a partir dos itens de uma listagem (ex.:
+    /// `Archivus::list_with_filter`)
+    ///
+    /// Os caminhos são gravados relativos a `root`. Com um algoritmo, o
+    /// conteúdo de cada arquivo comum é lido e seu hash é registrado.
+    ///
+    /// Retorna `ArchivusError::InvalidPath` para um caminho relativo que não
+    /// é UTF-8 válido (o formato binário guarda texto, e dois nomes
+    /// diferentes poderiam virar o mesmo) ou que aparece mais de uma vez.
+    pub fn from_files<P, I>(root: P, files: I, algorithm: Option<HashAlgorithm>) -> Result<Self, ArchivusError>
+    where
+        P: AsRef<Path>,
+        I: IntoIterator<Item = FileInfo>,
+    {
+        let root = root.as_ref();
+        let mut entries = Vec::new();
+        for file in files {
+            if file.path.strip_prefix(root).unwrap_or(&file.path).to_str().is_none() {
+                return Err(ArchivusError::InvalidPath(format!("nome não UTF-8 no snapshot: {}", file.path.display())));
+            }
+            let digest = match algorithm {
+                Some(algorithm) if file.file_type == FileType::Regular => Some(hash::hash_file(&file.path, algorithm)?),
+                _ => None,
+            };
+            let modified = match &file.unix {
+                Some(unix) => Some(unix.modified),
+                None => file.modified.map(|seconds| Timestamp::new(seconds as i64, 0)),
+            };
+            entries.push(SnapshotEntry {
+                path: relative_path(root, &file.path),
+                file_type: file.file_type,
+                size: file.size,
+                modified,
+                mode: file.unix.as_ref().map(|unix| unix.mode & 0o7777),
+                link_target: file.link_target.as_ref().map(|target| target.to_string_lossy().into_owned()),
+                digest,
+            });
+        }
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+        if let Some(pair) = entries.windows(2).find(|pair| pair[0].path == pair[1].path) {
+            return Err(ArchivusError::InvalidPath(format!("caminho repetido no snapshot: {}", pair[0].path)));
+        }
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            created: Timestamp::from(SystemTime::now()),
+            algorithm,
+            entries,
+        })
+    }
+
+    /// Procura uma entrada pelo caminho relativo
+    pub fn get(&self, path: &str) -> Option<&SnapshotEntry> {
+        self.entries
+            .binary_search_by(|entry| entry.path.as_str().cmp(path))
+            .ok()
+            .map(|index| &self.entries[index])
+    }
+
+    /// Grava o snapshot no formato binário
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), ArchivusError> {
+        fs::write(path, self.to_bytes())?;
+        Ok(())
+    }
+
+    /// Lê um snapshot gravado por `save`
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ArchivusError> {
+        Self::from_bytes(&fs::read(path)?)
+    }
+
+    /// Codifica o snapshot no formato binário
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(MAGIC);
+        out.extend_from_slice(&Self::VERSION.to_le_bytes());
+        write_timestamp(&mut out, self.created);
+        write_str(&mut out, &self.root.to_string_lossy());
+        out.push(match self.algorithm {
+            None => 0,
+            Some(algorithm) => HashAlgorithm::ALL.iter().position(|&a| a == algorithm).expect("algoritmo conhecido") as u8 + 1,
+        });
+        write_varint(&mut out, self.entries.len() as u64);
+
+        let mut previous = "";
+        for entry in &self.entries {
+            // Os caminhos ordenados costumam repetir o diretório do anterior
+            let mut shared = previous.bytes().zip(entry.path.bytes()).take_while(|(a, b)| a == b).count();
+            while !entry.path.is_char_boundary(shared) {
+                shared -= 1;
+            }
+            write_varint(&mut out, shared as u64);
+            write_str(&mut out, &entry.path[shared..]);
+            previous = &entry.path;
+
+            let mut flags = 0;
+            for (present, flag) in [
+                (entry.modified.is_some(), HAS_MODIFIED),
+                (entry.mode.is_some(), HAS_MODE),
+                (entry.link_target.is_some(), HAS_LINK_TARGET),
+                (entry.digest.is_some(), HAS_DIGEST),
+            ] {
+                if present {
+                    flags |= flag;
+                }
+            }
+            out.push(FILE_TYPES.iter().position(|&(t, _)| t == entry.file_type).expect("tipo conhecido") as u8);
+            out.push(flags);
+            write_varint(&mut out, entry.size);
+            if let Some(modified) = entry.modified {
+                write_timestamp(&mut out, modified);
+            }
+            if let Some(mode) = entry.mode {
+                write_varint(&mut out, mode as u64);
+            }
+            if let Some(target) = &entry.link_target {
+                write_str(&mut out, target);
+            }
+            if let Some(digest) = &entry.digest {
+                out.extend_from_slice(digest.as_bytes());
+            }
+        }
+        out
+    }
+
+    /// Decodifica um snapshot do formato binário
+    ///
+    /// # Erros
+    /// Retorna `ArchivusError::ParseError`, com a posição em bytes, se os
+    /// dados estiverem truncados ou corrompidos ou se a versão for mais nova
+    /// que `Snapshot::VERSION`.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ArchivusError> {
+        let mut decoder = Decoder { data, position: 0 };
+        if decoder.take(MAGIC.len())? != MAGIC {
+            return Err(decoder.error_at(0, "não é um snapshot do archivus"));
+        }
+        let version = u16::from_le_bytes(decoder.take(2)?.try_into().expect("dois bytes"));
+        if version == 0 || version > Self::VERSION {
+            return Err(decoder.error_at(MAGIC.len(), &format!("versão {} não suportada", version)));
+        }
+
+        let created = decoder.timestamp()?;
+        let root = PathBuf::from(decoder.string()?);
+        let algorithm = match decoder.byte()? {
+            0 => None,
+            code => Some(*HashAlgorithm::ALL.get(code as usize - 1).ok_or_else(|| decoder.error("algoritmo desconhecido"))?),
+        };
+
+        let count = decoder.varint()?;
+        let mut entries: Vec<SnapshotEntry> = Vec::with_capacity(count.min(4096) as usize);
+        let mut previous = String::new();
+        for _ in 0..count {
+            let entry_start = decoder.position;
+            let shared = decoder.varint()? as usize;
+            if shared > previous.len() || !previous.is_char_boundary(shared) {
+                return Err(decoder.error_at(entry_start, "prefixo de caminho inválido"));
+            }
+            let path = format!("{}{}", &previous[..shared], decoder.string()?);
+            if !entries.is_empty() && path <= previous {
+                return Err(decoder.error_at(entry_start, "entradas fora de ordem"));
+            }
+
+            let code = decoder.byte()?;
+            let file_type = FILE_TYPES.get(code as usize).map(|&(t, _)| t).ok_or_else(|| decoder.error("tipo de item desconhecido"))?;
+            let flags = decoder.byte()?;
+            if flags & !(HAS_MODIFIED | HAS_MODE | HAS_LINK_TARGET | HAS_DIGEST) != 0 {
+                return Err(decoder.error("flags desconhecidas"));
+            }
+            let size = decoder.varint()?;
+            let modified = if flags & HAS_MODIFIED != 0 { Some(decoder.timestamp()?) } else { None };
+            let mode = if flags & HAS_MODE != 0 {
+                Some(u32::try_from(decoder.varint()?).map_err(|_| decoder.error("modo inválido"))?)
+            } else {
+                None
+            };
+            let link_target = if flags & HAS_LINK_TARGET != 0 { Some(decoder.string()?) } else { None };
+            let digest = if flags & HAS_DIGEST != 0 {
+                let algorithm = algorithm.ok_or_else(|| decoder.error("hash em snapshot sem algoritmo"))?;
+                Some(Digest {
+                    algorithm,
+                    bytes: decoder.take(algorithm.digest_len())?.to_vec(),
+                })
+            } else {
+                None
+            };
+
+            previous.clone_from(&path);
+            entries.push(SnapshotEntry { path, file_type, size, modified, mode, link_target, digest });
+        }
+        if decoder.position != data.len() {
+            return Err(decoder.error("dados extras após o fim do snapshot"));
+        }
+
+        Ok(Self { root, created, algorithm, entries })
+    }
+
+    /// Exporta o snapshot para JSON, com uma entrada por linha
+    ///
+    /// Datas são objetos `{"seconds": .., "nanoseconds": ..}`, modos são
+    /// números (ex.: `420` para `0o644`) e hashes são hexadecimais; campos
+    /// ausentes são `null`.
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("{\n");
+        json.push_str(&format!("  \"version\": {},\n", Self::VERSION));
+        json.push_str(&format!("  \"root\": {},\n", json_string(&self.root.to_string_lossy())));
+        json.push_str(&format!("  \"created\": {},\n", json_timestamp(Some(self.created))));
+        json.push_str(&format!(
+            "  \"algorithm\": {},\n",
+            self.algorithm.map(|algorithm| json_string(algorithm.name())).unwrap_or_else(|| "null".to_string())
+        ));
+
+        if self.entries.is_empty() {
+            json.push_str("  \"entries\": []\n}\n");
+            return json;
+        }
+        json.push_str("  \"entries\": [\n");
+        for (index, entry) in self.entries.iter().enumerate() {
+            let type_name = FILE_TYPES.iter().find(|&&(t, _)| t == entry.file_type).map_or("unknown", |&(_, name)| name);
+            json.push_str(&format!(
+                "    {{\"path\": {}, \"type\": \"{}\", \"size\": {}, \"modified\": {}, \"mode\": {}, \"link_target\": {}, \"digest\": {}}}",
+                json_string(&entry.path),
+                type_name,
+                entry.size,
+                json_timestamp(entry.modified),
+                entry.mode.map(|mode| mode.to_string()).unwrap_or_else(|| "null".to_string()),
+                entry.link_target.as_deref().map(json_string).unwrap_or_else(|| "null".to_string()),
+                entry.digest.as_ref().map(|digest| json_string(&digest.to_hex())).unwrap_or_else(|| "null".to_string()),
+            ));
+            json.push_str(if index + 1 < self.entries.len() { ",\n" } else { "\n" });
+        }
+        json.push_str("  ]\n}\n");
+        json
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut json = String::with_capacity(text.len() + 2);
+    json.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+fn json_timestamp(timestamp: Option<Timestamp>) -> String {
+    match timestamp {
+        Some(timestamp) => format!("{{\"seconds\": {}, \"nanoseconds\": {}}}", timestamp.seconds, timestamp.nanoseconds),
+        None => "null".to_string(),
+    }
+}
+
+// ====================================================================
+// CODIFICAÇÃO BINÁRIA
+// ====================================================================
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn write_str(out: &mut Vec<u8>, text: &str) {
+    write_varint(out, text.len() as u64);
+    out.extend_from_slice(text.as_bytes());
+}
+
+fn write_timestamp(out: &mut Vec<u8>, timestamp: Timestamp) {
+    // zigzag: datas anteriores a 1970 continuam curtas
+    write_varint(out, ((timestamp.seconds << 1) ^ (timestamp.seconds >> 63)) as u64);
+    write_varint(out, timestamp.nanoseconds as u64);
+}
+
+struct Decoder<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Decoder<'a> {
+    fn error(&self, message: &str) -> ArchivusError {
+        self.error_at(self.position, message)
+    }
+
+    fn error_at(&self, position: usize, message: &str) -> ArchivusError {
+        ArchivusError::ParseError { position, message: message.to_string() }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ArchivusError> {
+        if self.data.len() - self.position < len {
+            return Err(self.error("snapshot truncado"));
+        }
+        let bytes = &self.data[self.position..self.position + len];
+        self.position += len;
+        Ok(bytes)
+    }
+
+    fn byte(&mut self) -> Result<u8, ArchivusError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn varint(&mut self) -> Result<u64, ArchivusError> {
+        let start = self.position;
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(self.error_at(start, "inteiro longo demais"))
+    }
+
+    fn string(&mut self) -> Result<String, ArchivusError> {
+        let start = self.position;
+        let len = usize::try_from(self.varint()?).map_err(|_| self.error_at(start, "texto longo demais"))?;
+        let bytes = self.take(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| self.error_at(start, "texto não é UTF-8"))
+    }
+
+    fn timestamp(&mut self) -> Result<Timestamp, ArchivusError> {
+        let zigzag = self.varint()?;
+        let seconds = (zigzag >> 1) as i64 ^ -((zigzag & 1) as i64);
+        let nanoseconds = self.varint()?;
+        if nanoseconds >= 1_000_000_000 {
+            return Err(self.error("nanossegundos fora do intervalo"));
+        }
+        Ok(Timestamp::new(seconds, nanoseconds as i64))
+    }
+}
+
+// ====================================================================
+// TESTES UNITÁRIOS
+// ====================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::scratch_dir;
+    use crate::{Archivus, ContentHasher, FileFilter};
+
+    fn sample() -> Snapshot {
+        let entry = |path: &str, file_type| SnapshotEntry {
+            path: path.to_string(),
+            file_type,
+            size: 0,
+            modified: None,
+            mode: None,
+            link_target: None,
+            digest: None,
+        };
+        Snapshot {
+            root: PathBuf::from("/srv/app"),
+            created: Timestamp::new(-5, 250),
+            algorithm: Some(HashAlgorithm::Crc32),
+            entries: vec![
+                SnapshotEntry {
+                    size: 300,
+                    modified: Some(Timestamp::new(1_700_000_000, 123_456_789)),
+                    mode: Some(0o4755),
+                    digest: Some(ContentHasher::digest(HashAlgorithm::Crc32, b"123456789")),
+                    ..entry("bin/ação", FileType::Regular)
+                },
+                SnapshotEntry {
+                    link_target: Some("../bin/ação".to_string()),
+                    ..entry("bin/açúcar", FileType::Symlink)
+                },
+                entry("dados", FileType::Directory),
+                entry("dados/\"aspas\"\n", FileType::Fifo),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_snapshot_encoding() {
+        let snapshot = sample();
+        let bytes = snapshot.to_bytes();
+        assert!(bytes.starts_with(b"ARCHSNAP\x01\x00"));
+        assert_eq!(Snapshot::from_bytes(&bytes).unwrap(), snapshot);
+        assert_eq!(snapshot.get("bin/açúcar").unwrap().file_type, FileType::Symlink);
+        assert!(snapshot.get("bin").is_none());
+
+        // "bin/açúcar" reaproveita "bin/aç" de "bin/ação", sem cortar o "ú",
+        // que começa com o mesmo byte do "ã"
+        assert!(bytes.windows(7).any(|w| w == "\x07\x05úcar".as_bytes()));
+
+        let error_position = |data: &[u8]| match Snapshot::from_bytes(data) {
+            Err(ArchivusError::ParseError { position, .. }) => position,
+            other => panic!("deveria falhar: {:?}", other),
+        };
+        assert_eq!(error_position(b"PNG\r\n\x1a\n\0\x01\x00"), 0);
+        let mut future = bytes.clone();
+        future[8] = 2;
+        assert_eq!(error_position(&future), 8);
+        assert_eq!(error_position(&bytes[..bytes.len() - 1]), bytes.len() - 1);
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert_eq!(error_position(&trailing), bytes.len());
+    }
+
+    #[test]
+    fn test_snapshot_json() {
+        let json = sample().to_json();
+        assert!(json.starts_with("{\n  \"version\": 1,\n  \"root\": \"/srv/app\",\n  \"created\": {\"seconds\": -5, \"nanoseconds\": 250},\n  \"algorithm\": \"crc32\",\n"));
+        assert!(json.contains(
+            "    {\"path\": \"bin/ação\", \"type\": \"file\", \"size\": 300, \"modified\": {\"seconds\": 1700000000, \"nanoseconds\": 123456789}, \"mode\": 2541, \"link_target\": null, \"digest\": \"cbf43926\"},\n"
+        ));
+        assert!(json.contains("\"path\": \"dados/\\\"aspas\\\"\\n\", \"type\": \"fifo\""));
+        assert!(json.ends_with("}\n  ]\n}\n"));
+
+        let empty = Snapshot { entries: Vec::new(), algorithm: None, ..sample() };
+        assert!(empty.to_json().ends_with("\"algorithm\": null,\n  \"entries\": []\n}\n"));
+    }
+
+    // Nomes que não são UTF-8 só podem ser criados em sistemas Unix
+    #[cfg(unix)]
+    #[test]
+    fn test_snapshot_rejects_ambiguous_paths() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let root = scratch_dir("snapshot_ambiguous");
+        fs::create_dir_all(&root).unwrap();
+        let names = [OsStr::from_bytes(b"x\xff"), OsStr::from_bytes(b"x\xfe")];
+        for name in names {
+            fs::write(root.join(name), "").unwrap();
+        }
+
+        // Os dois nomes virariam "x\u{FFFD}", e `load` recusaria o arquivo gravado
+        let files = names.map(|name| FileInfo::new(root.join(name)).unwrap());
+        assert!(matches!(Snapshot::from_files(&root, files, None), Err(ArchivusError::InvalidPath(_))));
+
+        fs::write(root.join("a"), "").unwrap();
+        let file = FileInfo::new(root.join("a")).unwrap();
+        assert!(matches!(Snapshot::from_files(&root, [file.clone(), file], None), Err(ArchivusError::InvalidPath(_))));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let utils = Archivus::new();
+        let root = scratch_dir("snapshot");
+        fs::create_dir_all(root.join("sub")).unwrap();
+        fs::write(root.join("a.txt"), "abc").unwrap();
+        fs::write(root.join("sub/b.txt"), "").unwrap();
+
+        let filter = FileFilter { recursive: true, ..Default::default() };
+        let snapshot = utils.snapshot(&root, &filter, Some(HashAlgorithm::Sha256)).unwrap();
+        let paths: Vec<&str> = snapshot.entries.iter().map(|entry| entry.path.as_str()).collect();
+        assert_eq!(paths, ["a.txt", "sub", "sub/b.txt"]);
+
+        let file = snapshot.get("a.txt").unwrap();
+        assert_eq!((file.file_type, file.size), (FileType::Regular, 3));
+        assert_eq!(file.digest.as_ref().unwrap().to_hex(), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        assert_eq!(file.modified.unwrap().seconds as u64, FileInfo::new(root.join("a.txt")).unwrap().modified.unwrap());
+        assert!(snapshot.get("sub").unwrap().digest.is_none());
+        if cfg!(unix) {
+            assert!(file.mode.unwrap() & 0o400 != 0);
+        }
+
+        let saved = root.join("sub/estado.snap");
+        snapshot.save(&saved).unwrap();
+        assert_eq!(Snapshot::load(&saved).unwrap(), snapshot);
+        assert!(snapshot.to_json().contains("\"path\": \"sub/b.txt\", \"type\": \"file\", \"size\": 0"));
+
+        let plain = utils.snapshot(&root, &filter, None).unwrap();
+        assert!(plain.algorithm.is_none() && plain.entries.iter().all(|entry| entry.digest.is_none()));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
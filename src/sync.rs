@@ -0,0 +1,512 @@
+// ====================================================================
+// SINCRONIZAÇÃO DE DIRETÓRIOS
+// ====================================================================
+// Espelhamento em um sentido, como o `rsync -a`: as diferenças entre
+// origem e destino (veja `diff`) viram um plano de ações, que pode ser
+// apenas listado (dry-run) ou executado
+
+use std::collections::HashSet;
+use std::fmt;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+use crate::{format_bytes, ArchivusError, Change, CompareMode, DiffReport, FileFilter, FileType, HashAlgorithm, Snapshot};
+
+/// Opções de `Archivus::sync`
+///
+/// # Exemplos
+/// ```rust
+/// use archivus::{CompareMode, SyncOptions};
+///
+/// // Espelho exato, comparando o conteúdo, sem alterar nada
+/// let options = SyncOptions {
+///     compare: CompareMode::Content,
+///     delete: true,
+///     dry_run: true,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone)]
+pub struct SyncOptions {
+    /// Critério para atualizar um arquivo que existe nos dois lados
+    pub compare: CompareMode,
+    /// Algoritmo usado com `CompareMode::Content`
+    pub algorithm: HashAlgorithm,
+    /// Remove do destino os itens que não existem na origem
+    pub delete: bool,
+    /// Copia a data de modificação dos arquivos e diretórios
+    pub preserve_times: bool,
+    /// Copia as permissões dos arquivos e diretórios, inclusive as de itens
+    /// que só diferem nisso
+    pub preserve_permissions: bool,
+    /// Apenas monta o plano, sem alterar o destino
+    pub dry_run: bool,
+    /// Itens considerados nos dois lados; itens excluídos do destino nunca
+    /// são removidos. Links simbólicos nunca são seguidos: são recriados
+    /// como links
+    pub filter: FileFilter,
+}
+
+impl Default for SyncOptions {
+    fn default() -> Self {
+        Self {
+            compare: CompareMode::Metadata,
+            algorithm: HashAlgorithm::XxHash64,
+            delete: false,
+            preserve_times: true,
+            preserve_permissions: true,
+            dry_run: false,
+            filter: FileFilter {
+                recursive: true,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// Ação de uma sincronização, com o caminho relativo às raízes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncAction {
+    /// Cria um diretório que só existe na origem
+    CreateDirectory(String),
+    /// Copia um arquivo que só existe na origem
+    Copy { path: String, size: u64 },
+    /// Substitui um arquivo diferente no destino
+    Update { path: String, size: u64 },
+    /// Cria (ou recria) um link simbólico com o destino do link da origem
+    Symlink { path: String, target: String },
+    /// Remove um item do destino, extra ou de tipo diferente do da origem
+    Remove { path: String, is_directory: bool },
+    /// Copia as permissões de um item que só difere nelas
+    SetPermissions(String),
+}
+
+impl fmt::Display for SyncAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SyncAction::CreateDirectory(path) => write!(f, "criar diretório {}", path),
+            SyncAction::Copy { path, size } => write!(f, "copiar {} ({})", path, format_bytes(*size)),
+            SyncAction::Update { path, size } => write!(f, "atualizar {} ({})", path, format_bytes(*size)),
+            SyncAction::Symlink { path, target } => write!(f, "criar link {} -> {}", path, target),
+            SyncAction::Remove { path, is_directory: true } => write!(f, "remover diretório {}", path),
+            SyncAction::Remove { path, is_directory: false } => write!(f, "remover {}", path),
+            SyncAction::SetPermissions(path) => write!(f, "ajustar permissões de {}", path),
+        }
+    }
+}
+
+/// Resultado de `Archivus::sync`
+///
+/// Em um dry-run, nada é alterado e os contadores indicam o que seria
+/// transferido.
+#[derive(Debug, Clone, Default)]
+pub struct SyncReport {
+    /// Ações, na ordem de execução
+    pub actions: Vec<SyncAction>,
+    /// O destino não foi alterado
+    pub dry_run: bool,
+    /// Arquivos copiados ou atualizados
+    pub files_copied: usize,
+    /// Bytes copiados
+    pub bytes_copied: u64,
+    /// Diretórios criados
+    pub directories_created: usize,
+    /// Links simbólicos criados
+    pub symlinks_created: usize,
+    /// Itens removidos do destino
+    pub removed: usize,
+    /// Itens que tiveram apenas as permissões ajustadas
+    pub permissions_updated: usize,
+    /// Itens já iguais nos dois lados
+    pub unchanged: usize,
+}
+
+impl SyncReport {
+    /// Formata os bytes copiados de forma legível
+    pub fn formatted_bytes_copied(&self) -> String {
+        format_bytes(self.bytes_copied)
+    }
+}
+
+/// Caminhos dos arquivos e diretórios presentes nos dois lados, com o mesmo
+/// tipo, cujas permissões diferem
+///
+/// A comparação de `diff` não considera permissões, então essas diferenças
+/// são procuradas à parte.
+pub(crate) fn permission_changes(source: &Snapshot, destination: &Snapshot) -> Vec<String> {
+    source
+        .entries
+        .iter()
+        .filter(|entry| matches!(entry.file_type, FileType::Regular | FileType::Directory) && entry.mode.is_some())
+        .filter(|entry| {
+            destination
+                .get(&entry.path)
+                .is_some_and(|other| other.file_type == entry.file_type && other.mode.is_some() && other.mode != entry.mode)
+        })
+        .map(|entry| entry.path.clone())
+        .collect()
+}
+
+/// Monta o plano a partir das diferenças entre origem (esquerda) e destino
+/// (direita) e dos itens que diferem apenas nas permissões
+///
+/// Remoções vêm primeiro, com o conteúdo antes do diretório; depois os
+/// diretórios são criados, de cima para baixo, os arquivos copiados e, por
+/// fim, as permissões ajustadas.
+pub(crate) fn plan(diff: &DiffReport, delete: bool, permissions: Vec<String>) -> Vec<SyncAction> {
+    let mut removals = Vec::new();
+    let mut creations = Vec::new();
+    for change in &diff.changes {
+        let source = match change {
+            Change::Removed(source) => source,
+            Change::Modified { left, .. } => left,
+            Change::TypeChanged { left, right } => {
+                removals.push(SyncAction::Remove { path: right.path.clone(), is_directory: right.file_type == FileType::Directory });
+                left
+            }
+            Change::Added(extra) => {
+                if delete {
+                    removals.push(SyncAction::Remove { path: extra.path.clone(), is_directory: extra.file_type == FileType::Directory });
+                }
+                continue;
+            }
+            Change::Renamed { .. } => unreachable!("a sincronização não detecta renomeações"),
+        };
+
+        let path = source.path.clone();
+        creations.push(match (source.file_type, change) {
+            (FileType::Directory, _) => SyncAction::CreateDirectory(path),
+            (FileType::Regular, Change::Modified { .. }) => SyncAction::Update { path, size: source.size },
+            (FileType::Regular, _) => SyncAction::Copy { path, size: source.size },
+            (FileType::Symlink, _) => SyncAction::Symlink { path, target: source.link_target.clone().unwrap_or_default() },
+            // Pipes, sockets e dispositivos não são copiados
+            _ => continue,
+        });
+    }
+
+    // Um arquivo atualizado já recebe as permissões da origem
+    let updated: HashSet<String> = creations
+        .iter()
+        .filter_map(|action| match action {
+            SyncAction::Update { path, .. } => Some(path.clone()),
+            _ => None,
+        })
+        .collect();
+    creations.extend(permissions.into_iter().filter(|path| !updated.contains(path)).map(SyncAction::SetPermissions));
+
+    let path = |action: &SyncAction| match action {
+        SyncAction::CreateDirectory(path)
+        | SyncAction::Copy { path, .. }
+        | SyncAction::Update { path, .. }
+        | SyncAction::Symlink { path, .. }
+        | SyncAction::Remove { path, .. }
+        | SyncAction::SetPermissions(path) => path.clone(),
+    };
+    let rank = |action: &SyncAction| match action {
+        SyncAction::CreateDirectory(_) => 0,
+        SyncAction::SetPermissions(_) => 2,
+        _ => 1,
+    };
+    removals.sort_by_key(|action| std::cmp::Reverse(path(action)));
+    creations.sort_by_key(|action| (rank(action), path(action)));
+    removals.extend(creations);
+    removals
+}
+
+/// Executa o plano (ou apenas conta as ações, em um dry-run)
+pub(crate) fn execute(source: &Path, destination: &Path, actions: Vec<SyncAction>, unchanged: usize, options: &SyncOptions) -> Result<SyncReport, ArchivusError> {
+    // Itens que só diferem nas permissões foram contados como iguais no diff
+    let adjusted = actions.iter().filter(|action| matches!(action, SyncAction::SetPermissions(_))).count();
+    let mut report = SyncReport { dry_run: options.dry_run, unchanged: unchanged.saturating_sub(adjusted), ..Default::default() };
+    if !options.dry_run {
+        fs::create_dir_all(destination)?;
+    }
+
+    let mut created_directories = Vec::new();
+    for action in &actions {
+        match action {
+            SyncAction::Remove { path, is_directory } => {
+                let target = destination.join(path);
+                if !options.dry_run {
+                    let result = if *is_directory { fs::remove_dir(&target) } else { fs::remove_file(&target) };
+                    match result {
+                        // Um diretório que precisa dar lugar a um arquivo ou link é
+                        // removido por inteiro; um diretório extra com itens
+                        // excluídos pelo filtro é mantido
+                        Err(e) if *is_directory && e.kind() == io::ErrorKind::DirectoryNotEmpty => {
+                            let replaced = actions.iter().any(|other| {
+                                matches!(other, SyncAction::Copy { path: p, .. } | SyncAction::Symlink { path: p, .. } if p == path)
+                            });
+                            if !replaced {
+                                continue;
+                            }
+                            fs::remove_dir_all(&target)?;
+                        }
+                        result => result?,
+                    }
+                }
+                report.removed += 1;
+            }
+            SyncAction::CreateDirectory(path) => {
+                if !options.dry_run {
+                    fs::create_dir(destination.join(path))?;
+                    created_directories.push(path);
+                }
+                report.directories_created += 1;
+            }
+            SyncAction::Copy { path, size } | SyncAction::Update { path, size } => {
+                if !options.dry_run {
+                    copy(&source.join(path), &destination.join(path), options)?;
+                }
+                report.files_copied += 1;
+                report.bytes_copied += size;
+            }
+            SyncAction::Symlink { path, target } => {
+                if !options.dry_run {
+                    symlink(target, &destination.join(path))?;
+                }
+                report.symlinks_created += 1;
+            }
+            SyncAction::SetPermissions(path) => {
+                if !options.dry_run {
+                    fs::set_permissions(destination.join(path), fs::metadata(source.join(path))?.permissions())?;
+                }
+                report.permissions_updated += 1;
+            }
+        }
+    }
+
+    // Permissões e datas dos diretórios por último: criar o conteúdo altera a
+    // data do diretório, e um diretório somente leitura impediria a cópia
+    for path in created_directories.into_iter().rev() {
+        let metadata = fs::metadata(source.join(path))?;
+        if options.preserve_permissions {
+            fs::set_permissions(destination.join(path), metadata.permissions())?;
+        }
+        if options.preserve_times && cfg!(unix) {
+            File::open(destination.join(path))?.set_modified(metadata.modified()?)?;
+        }
+    }
+
+    report.actions = actions;
+    Ok(report)
+}
+
+// Caminho canônico de um item que pode ainda não existir: o ancestral
+// existente mais próximo é canonicalizado e os componentes que faltam são
+// reaplicados por cima dele
+pub(crate) fn resolve(path: &Path) -> Option<PathBuf> {
+    let absolute = std::path::absolute(path).ok()?;
+    let (existing, mut resolved) = absolute.ancestors().find_map(|ancestor| Some((ancestor, ancestor.canonicalize().ok()?)))?;
+    for component in absolute.strip_prefix(existing).ok()?.components() {
+        match component {
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            Component::Normal(name) => resolved.push(name),
+            _ => {}
+        }
+    }
+    Some(resolved)
+}
+
+// Copia para um arquivo temporário no mesmo diretório e o renomeia, para que
+// o destino nunca fique com um arquivo pela metade
+fn copy(from: &Path, to: &Path, options: &SyncOptions) -> Result<(), ArchivusError> {
+    let metadata = fs::metadata(from)?;
+    let name = to.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+    let temporary: PathBuf = to.with_file_name(format!(".{}.archivus-tmp", name));
+
+    let result = (|| -> Result<(), ArchivusError> {
+        let mut output = File::create(&temporary)?;
+        io::copy(&mut File::open(from)?, &mut output)?;
+        if options.preserve_times {
+            output.set_modified(metadata.modified()?)?;
+        }
+        if options.preserve_permissions {
+            output.set_permissions(metadata.permissions())?;
+        }
+        drop(output);
+        fs::rename(&temporary, to)?;
+        Ok(())
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&temporary);
+    }
+    result
+}
+
+fn symlink(target: &str, link: &Path) -> Result<(), ArchivusError> {
+    if fs::symlink_metadata(link).is_ok() {
+        fs::remove_file(link)?;
+    }
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(target, link)?;
+    #[cfg(windows)]
+    std::os::windows::fs::symlink_file(target, link)?;
+    Ok(())
+}
+
+// ====================================================================
+// TESTES UNITÁRIOS
+// ====================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::scratch_dir;
+    use crate::{Archivus, DiffOptions, SnapshotEntry, Timestamp};
+    use std::time::{Duration, SystemTime};
+
+    fn entry(path: &str, file_type: FileType) -> SnapshotEntry {
+        SnapshotEntry {
+            path: path.to_string(),
+            file_type,
+            size: 10,
+            modified: Some(Timestamp::new(0, 0)),
+            mode: None,
+            link_target: (file_type == FileType::Symlink).then(|| "alvo".to_string()),
+            digest: None,
+        }
+    }
+
+    #[test]
+    fn test_sync_plan() {
+        let diff = DiffReport {
+            left_root: PathBuf::from("origem"),
+            right_root: PathBuf::from("destino"),
+            changes: vec![
+                Change::Removed(entry("a", FileType::Directory)),
+                Change::Removed(entry("a/b", FileType::Directory)),
+                Change::Removed(entry("a/b/c.txt", FileType::Regular)),
+                Change::Modified { left: entry("d.txt", FileType::Regular), right: entry("d.txt", FileType::Regular) },
+                Change::Added(entry("extra", FileType::Directory)),
+                Change::Added(entry("extra/e.txt", FileType::Regular)),
+                Change::Removed(entry("fifo", FileType::Fifo)),
+                Change::TypeChanged { left: entry("link", FileType::Symlink), right: entry("link", FileType::Directory) },
+            ],
+            unchanged: 0,
+        };
+
+        let remove = |path: &str, is_directory| SyncAction::Remove { path: path.to_string(), is_directory };
+        let creations = [
+            SyncAction::CreateDirectory("a".to_string()),
+            SyncAction::CreateDirectory("a/b".to_string()),
+            SyncAction::Copy { path: "a/b/c.txt".to_string(), size: 10 },
+            SyncAction::Update { path: "d.txt".to_string(), size: 10 },
+            SyncAction::Symlink { path: "link".to_string(), target: "alvo".to_string() },
+        ];
+        assert_eq!(plan(&diff, false, Vec::new()), [vec![remove("link", true)], creations.to_vec()].concat());
+        assert_eq!(
+            plan(&diff, true, Vec::new()),
+            [vec![remove("link", true), remove("extra/e.txt", false), remove("extra", true)], creations.to_vec()].concat()
+        );
+
+        // Permissões por último; um arquivo atualizado já as recebe
+        let permissions = plan(&diff, false, vec!["a".to_string(), "d.txt".to_string(), "e".to_string()]);
+        assert_eq!(permissions[permissions.len() - 2..], [SyncAction::SetPermissions("a".to_string()), SyncAction::SetPermissions("e".to_string())]);
+
+        assert_eq!(creations[2].to_string(), "copiar a/b/c.txt (10 B)");
+        assert_eq!(remove("extra", true).to_string(), "remover diretório extra");
+    }
+
+    #[test]
+    fn test_resolve_missing_path() {
+        let root = scratch_dir("sync_resolve");
+        fs::create_dir_all(root.join("existe")).unwrap();
+        let canonical = root.canonicalize().unwrap();
+
+        assert_eq!(resolve(&root.join("existe")), Some(canonical.join("existe")));
+        assert_eq!(resolve(&root.join("novo/fundo")), Some(canonical.join("novo/fundo")));
+        assert_eq!(resolve(&root.join("novo/../existe/./fundo")), Some(canonical.join("existe/fundo")));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_sync() {
+        let utils = Archivus::new();
+        let root = scratch_dir("sync");
+        let (source, destination) = (root.join("origem"), root.join("destino"));
+        fs::create_dir_all(source.join("sub/vazio")).unwrap();
+        fs::write(source.join("a.txt"), "abc").unwrap();
+        fs::write(source.join("sub/b.txt"), "conteúdo").unwrap();
+        let time = SystemTime::UNIX_EPOCH + Duration::from_secs(1_600_000_000);
+        fs::File::options().write(true).open(source.join("a.txt")).unwrap().set_modified(time).unwrap();
+
+        let report = utils.sync(&source, &destination, &SyncOptions::default()).unwrap();
+        assert_eq!((report.files_copied, report.bytes_copied, report.directories_created), (2, 12, 2));
+        assert_eq!(fs::read_to_string(destination.join("sub/b.txt")).unwrap(), "conteúdo");
+        assert_eq!(fs::metadata(destination.join("a.txt")).unwrap().modified().unwrap(), time);
+        assert!(utils.diff(&source, &destination, &DiffOptions::default()).unwrap().is_empty());
+
+        // Nada a fazer na segunda vez
+        let report = utils.sync(&source, &destination, &SyncOptions::default()).unwrap();
+        assert!(report.actions.is_empty());
+        assert_eq!(report.unchanged, 4);
+
+        // Dry-run: o plano é montado, mas o destino não muda
+        fs::write(source.join("a.txt"), "abcd").unwrap();
+        fs::write(destination.join("extra.txt"), "x").unwrap();
+        let options = SyncOptions { delete: true, dry_run: true, ..Default::default() };
+        let plan = utils.sync(&source, &destination, &options).unwrap();
+        let actions: Vec<String> = plan.actions.iter().map(SyncAction::to_string).collect();
+        assert_eq!(actions, ["remover extra.txt", "atualizar a.txt (4 B)"]);
+        assert_eq!((plan.files_copied, plan.removed, plan.formatted_bytes_copied()), (1, 1, "4 B".to_string()));
+        assert!(destination.join("extra.txt").exists());
+        assert_eq!(fs::read_to_string(destination.join("a.txt")).unwrap(), "abc");
+
+        let report = utils.sync(&source, &destination, &SyncOptions { dry_run: false, ..options.clone() }).unwrap();
+        assert_eq!(report.actions, plan.actions);
+        assert!(!destination.join("extra.txt").exists());
+        assert!(utils.diff(&source, &destination, &DiffOptions::default()).unwrap().is_empty());
+
+        // Mesmo tamanho e mesma data: apenas a comparação por conteúdo percebe
+        fs::write(source.join("sub/b.txt"), "CONTEÚDO").unwrap();
+        let modified = fs::metadata(destination.join("sub/b.txt")).unwrap().modified().unwrap();
+        fs::File::options().write(true).open(source.join("sub/b.txt")).unwrap().set_modified(modified).unwrap();
+        assert!(utils.sync(&source, &destination, &SyncOptions::default()).unwrap().actions.is_empty());
+        let content = SyncOptions { compare: CompareMode::Content, ..Default::default() };
+        assert_eq!(utils.sync(&source, &destination, &content).unwrap().files_copied, 1);
+        assert_eq!(fs::read_to_string(destination.join("sub/b.txt")).unwrap(), "CONTEÚDO");
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::{symlink, PermissionsExt};
+
+            // Links são recriados, inclusive os que apontam para fora da
+            // origem, cujo conteúdo não é copiado
+            fs::create_dir_all(root.join("fora")).unwrap();
+            fs::write(root.join("fora/segredo.txt"), "x").unwrap();
+            symlink("a.txt", source.join("link")).unwrap();
+            symlink(root.join("fora"), source.join("externo")).unwrap();
+            let report = utils.sync(&source, &destination, &SyncOptions::default()).unwrap();
+            assert_eq!((report.symlinks_created, report.files_copied), (2, 0));
+            assert_eq!(fs::read_link(destination.join("link")).unwrap(), Path::new("a.txt"));
+            assert_eq!(fs::read_link(destination.join("externo")).unwrap(), root.join("fora"));
+            assert!(utils.sync(&source, &destination, &SyncOptions::default()).unwrap().actions.is_empty());
+
+            // Apenas as permissões mudaram
+            fs::set_permissions(source.join("a.txt"), fs::Permissions::from_mode(0o600)).unwrap();
+            fs::set_permissions(source.join("sub"), fs::Permissions::from_mode(0o700)).unwrap();
+            let report = utils.sync(&source, &destination, &SyncOptions::default()).unwrap();
+            let actions: Vec<String> = report.actions.iter().map(SyncAction::to_string).collect();
+            assert_eq!(actions, ["ajustar permissões de a.txt", "ajustar permissões de sub"]);
+            assert_eq!((report.permissions_updated, report.files_copied, report.unchanged), (2, 0, 4));
+            assert_eq!(fs::metadata(destination.join("a.txt")).unwrap().permissions().mode() & 0o7777, 0o600);
+            assert_eq!(fs::metadata(destination.join("sub")).unwrap().permissions().mode() & 0o7777, 0o700);
+            let keep = SyncOptions { preserve_permissions: false, ..Default::default() };
+            fs::set_permissions(source.join("a.txt"), fs::Permissions::from_mode(0o644)).unwrap();
+            assert!(utils.sync(&source, &destination, &keep).unwrap().actions.is_empty());
+        }
+
+        assert!(matches!(utils.sync(&source, source.join("sub/copia"), &options), Err(ArchivusError::InvalidPath(_))));
+        // Vários níveis do destino ainda não existem
+        assert!(matches!(utils.sync(&source, source.join("novo/fundo"), &options), Err(ArchivusError::InvalidPath(_))));
+        assert!(matches!(utils.sync(&source, root.join("novo/../origem/fundo"), &options), Err(ArchivusError::InvalidPath(_))));
+        assert!(!source.join("novo").exists());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
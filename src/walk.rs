@@ -0,0 +1,671 @@
+// ====================================================================
+// PERCURSO PREGUIÇOSO DE DIRETÓRIOS
+// ====================================================================
+// Iterador que percorre uma árvore de diretórios sob demanda, sem
+// acumular os resultados em memória
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::ignore::{IgnoreLevel, IgnoreRules};
+use crate::parallel::ParallelWalker;
+use crate::{device_id, ArchivusError, FileFilter, FileInfo};
+
+/// Política de tratamento de links simbólicos durante o percurso
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SymlinkPolicy {
+    /// Não segue links: o `FileInfo` descreve o próprio link e links para
+    /// diretórios nunca são percorridos
+    NoFollow,
+    /// Segue links para arquivos e diretórios, onde quer que apontem
+    #[default]
+    Follow,
+    /// Segue apenas links cujo destino está dentro do diretório raiz da busca;
+    /// os demais são tratados como em `NoFollow`
+    FollowWithinRoot,
+}
+
+/// Falha em um item do percurso, com o caminho que a causou
+pub(crate) type Failure = (PathBuf, ArchivusError);
+
+// Identifica um diretório de forma única para detectar ciclos
+#[cfg(unix)]
+pub(crate) type DirId = (u64, u64);
+#[cfg(not(unix))]
+pub(crate) type DirId = PathBuf;
+
+#[cfg(unix)]
+fn dir_id(path: &Path) -> Option<DirId> {
+    use std::os::unix::fs::MetadataExt;
+
+    fs::metadata(path).ok().map(|m| (m.dev(), m.ino()))
+}
+
+#[cfg(not(unix))]
+fn dir_id(path: &Path) -> Option<DirId> {
+    fs::canonicalize(path).ok()
+}
+
+// Entradas de um diretório aberto, na ordem em que serão visitadas
+pub(crate) type DirEntries = Box<dyn Iterator<Item = io::Result<PathBuf>> + Send>;
+
+// Abre um diretório, ordenando as entradas por nome quando pedido
+pub(crate) fn read_dir_entries(path: &Path, sorted: bool) -> Result<DirEntries, Failure> {
+    let entries = fs::read_dir(path)
+        .map_err(|e| (path.to_path_buf(), e.into()))?
+        .map(|entry| entry.map(|e| e.path()));
+
+    if sorted {
+        let mut paths = Vec::new();
+        let mut errors = Vec::new();
+        for entry in entries {
+            match entry {
+                Ok(path) => paths.push(path),
+                Err(e) => errors.push(Err(e)),
+            }
+        }
+        paths.sort();
+        Ok(Box::new(paths.into_iter().map(Ok).chain(errors)))
+    } else {
+        Ok(Box::new(entries))
+    }
+}
+
+/// Diretório a ser aberto durante o percurso
+pub(crate) struct PendingDir {
+    pub(crate) path: PathBuf,
+    pub(crate) depth: usize,
+    pub(crate) id: Option<DirId>,
+    /// Regras de exclusão dos diretórios acima deste
+    pub(crate) ignore: Option<Arc<IgnoreLevel>>,
+}
+
+/// Critério extra, interno à biblioteca, para não percorrer um diretório
+pub(crate) type Prune = Arc<dyn Fn(&FileInfo) -> bool + Send + Sync>;
+
+/// Regras do percurso compartilhadas entre o `Walker` e o `ParallelWalker`
+pub(crate) struct Traversal {
+    pub(crate) filter: FileFilter,
+    root_canonical: Option<PathBuf>,
+    // Dispositivo da raiz, quando a busca não atravessa pontos de montagem
+    root_device: Option<u64>,
+    global_ignore: Option<IgnoreRules>,
+    prune: Option<Prune>,
+}
+
+impl Traversal {
+    pub(crate) fn new(filter: &FileFilter) -> Self {
+        Self {
+            filter: filter.clone(),
+            root_canonical: None,
+            root_device: None,
+            global_ignore: None,
+            prune: None,
+        }
+    }
+
+    pub(crate) fn follows_links(&self) -> bool {
+        self.filter.symlinks != SymlinkPolicy::NoFollow
+    }
+
+    // Prepara a raiz da busca, que ainda não foi aberta
+    pub(crate) fn root(&mut self, root: &Path) -> PendingDir {
+        self.global_ignore = self.filter.global_ignore
+            .as_ref()
+            .map(|patterns| IgnoreRules::new(root, patterns));
+
+        if self.filter.same_filesystem {
+            self.root_device = fs::metadata(root).ok().as_ref().and_then(device_id);
+        }
+
+        let id = if self.follows_links() {
+            self.root_canonical = fs::canonicalize(root).ok();
+            dir_id(root)
+        } else {
+            None
+        };
+
+        PendingDir {
+            path: root.to_path_buf(),
+            depth: 1,
+            id,
+            ignore: None,
+        }
+    }
+
+    // Regras de exclusão válidas para o conteúdo de um diretório que está
+    // sendo aberto
+    pub(crate) fn ignore_rules(&self, dir: &PendingDir) -> Option<Arc<IgnoreLevel>> {
+        if self.filter.respect_ignore_files {
+            IgnoreLevel::load(&dir.path, dir.ignore.clone())
+        } else {
+            None
+        }
+    }
+
+    // Verifica se uma entrada é oculta (quando não incluídas), é um diretório
+    // podado ou é excluída pelos arquivos de exclusão ou pela lista global.
+    // Entradas excluídas não são produzidas nem percorridas
+    pub(crate) fn is_ignored(&self, ignore: Option<&IgnoreLevel>, file_info: &FileInfo) -> bool {
+        if file_info.is_hidden && !self.filter.include_hidden {
+            return true;
+        }
+
+        if file_info.is_directory
+            && let Some(ref pruned) = self.filter.prune_directories
+            && pruned.iter().any(|pattern| pattern.matches(&file_info.name))
+        {
+            return true;
+        }
+
+        ignore
+            .and_then(|level| level.matched(&file_info.path, file_info.is_directory))
+            .or_else(|| {
+                self.global_ignore
+                    .as_ref()
+                    .and_then(|rules| rules.matched(&file_info.path, file_info.is_directory))
+            })
+            .unwrap_or(false)
+    }
+
+    // Verifica se um link aponta para fora da raiz, quando apenas links
+    // internos devem ser seguidos. Links quebrados também não são seguidos
+    fn leaves_root(&self, path: &Path) -> bool {
+        if self.filter.symlinks != SymlinkPolicy::FollowWithinRoot
+            || !fs::symlink_metadata(path).is_ok_and(|m| m.file_type().is_symlink())
+        {
+            return false;
+        }
+
+        match (&self.root_canonical, fs::canonicalize(path)) {
+            (Some(root), Ok(target)) => !target.starts_with(root),
+            _ => true,
+        }
+    }
+
+    // Lê os metadados de uma entrada conforme a política de links
+    pub(crate) fn read_entry(&self, path: PathBuf, depth: usize) -> Result<FileInfo, Failure> {
+        let file_info = if self.follows_links() && !self.leaves_root(&path) {
+            FileInfo::new(&path)
+        } else {
+            FileInfo::new_no_follow(&path)
+        };
+
+        match file_info {
+            Ok(mut file_info) => {
+                file_info.depth = depth;
+                Ok(file_info)
+            }
+            Err(e) => Err((path, e)),
+        }
+    }
+
+    // Decide se o conteúdo de uma entrada deve ser percorrido e, nesse caso,
+    // prepara o diretório para ser aberto. `is_ancestor` indica se um
+    // diretório já está no caminho entre a raiz e a entrada
+    pub(crate) fn descend_into<F>(&self, file_info: &FileInfo, ignore: Option<&Arc<IgnoreLevel>>, is_ancestor: F) -> Option<PendingDir>
+    where
+        F: Fn(&DirId) -> bool,
+    {
+        if !self.filter.recursive
+            || !file_info.is_directory
+            || self.filter.max_depth.is_some_and(|max| file_info.depth >= max)
+            || self.prune.as_ref().is_some_and(|prune| prune(file_info))
+            || self.root_device.is_some_and(|device| file_info.device != Some(device))
+        {
+            return None;
+        }
+
+        if file_info.is_symlink && self.filter.symlinks == SymlinkPolicy::FollowWithinRoot {
+            let root = self.root_canonical.as_ref()?;
+            let target = fs::canonicalize(&file_info.path).ok()?;
+            if !target.starts_with(root) {
+                return None;
+            }
+        }
+
+        let id = if self.follows_links() {
+            let id = dir_id(&file_info.path);
+            if id.as_ref().is_some_and(&is_ancestor) {
+                return None;
+            }
+            id
+        } else {
+            None
+        };
+
+        Some(PendingDir {
+            path: file_info.path.clone(),
+            depth: file_info.depth + 1,
+            id,
+            ignore: ignore.cloned(),
+        })
+    }
+}
+
+// Diretório aberto, com a profundidade das entradas que contém
+struct Frame {
+    path: PathBuf,
+    entries: DirEntries,
+    depth: usize,
+    id: Option<DirId>,
+    ignore: Option<Arc<IgnoreLevel>>,
+}
+
+/// Iterador preguiçoso sobre as entradas de um diretório
+///
+/// Cada chamada a `next()` lê apenas o necessário para produzir a próxima
+/// entrada que satisfaz o `FileFilter`. Subdiretórios só são abertos quando
+/// o iterador chega até eles, então interromper a iteração (com `take`,
+/// `find`, `break`...) evita percorrer o restante da árvore.
+///
+/// A ordem segue a busca em profundidade: um diretório é produzido antes
+/// do seu conteúdo. Diretórios que não passam no filtro continuam sendo
+/// percorridos quando `recursive` está ativo, até o limite de `max_depth`.
+/// Cada `FileInfo` produzido traz a sua profundidade em `depth`. Com
+/// `FileFilter::sorted`, as entradas de cada diretório são visitadas em
+/// ordem de nome, o que resulta na ordem por caminho da árvore inteira.
+///
+/// Com `FileFilter::respect_ignore_files`, os arquivos `.gitignore`, `.ignore`
+/// e `.archivusignore` de cada diretório são lidos ao abri-lo e as suas
+/// regras valem para tudo abaixo dele, como no Git. `FileFilter::global_ignore`
+/// acrescenta regras relativas à raiz, com a menor prioridade. Entradas
+/// excluídas não são produzidas e diretórios excluídos não são abertos.
+///
+/// Links simbólicos seguem `FileFilter::symlinks`. Quando links são seguidos,
+/// cada diretório é identificado por dispositivo + inode: um diretório que
+/// já é ancestral do ponto atual é produzido normalmente, mas não é percorrido
+/// de novo, o que impede ciclos infinitos.
+///
+/// O `Walker` é sempre sequencial e ignora `FileFilter::threads`; veja
+/// `ParallelWalker` para o percurso com várias threads.
+///
+/// # Exemplos
+/// ```rust,no_run
+/// use archivus::{Archivus, FileFilter};
+///
+/// let utils = Archivus::new();
+/// let filter = FileFilter {
+///     extensions: Some(vec!["log".to_string()]),
+///     recursive: true,
+///     ..Default::default()
+/// };
+///
+/// // Para no primeiro arquivo de log grande, sem varrer o resto do volume
+/// let grande = utils.walk("/var/data", &filter)
+///     .filter_map(Result::ok)
+///     .find(|f| f.size > 1024 * 1024 * 1024);
+/// ```
+///
+/// # Erros
+/// Falhas de leitura são produzidas como `Err` no ponto em que ocorrem.
+/// O iterador pode continuar sendo consumido depois de um erro: o percurso
+/// segue a partir da próxima entrada. As funções `*_partial` de `Archivus`
+/// usam esse comportamento para coletar as falhas junto com os resultados.
+pub struct Walker {
+    traversal: Traversal,
+    root: Option<PathBuf>,
+    stack: Vec<Frame>,
+    pending_dir: Option<PendingDir>,
+}
+
+impl Walker {
+    /// Cria um novo Walker a partir de um diretório raiz
+    ///
+    /// A raiz não é produzida pelo iterador, apenas o seu conteúdo. Nenhuma
+    /// leitura é feita até a primeira chamada a `next()`.
+    pub fn new<P: AsRef<Path>>(root: P, filter: &FileFilter) -> Self {
+        Self {
+            traversal: Traversal::new(filter),
+            root: Some(root.as_ref().to_path_buf()),
+            stack: Vec::new(),
+            pending_dir: None,
+        }
+    }
+
+    // Impede que diretórios para os quais `prune` retorna true sejam percorridos
+    pub(crate) fn prune_with(mut self, prune: Prune) -> Self {
+        self.traversal.prune = Some(prune);
+        self
+    }
+
+    // Abre o próximo diretório pendente, se houver
+    fn open_pending(&mut self) -> Result<(), Failure> {
+        if let Some(root) = self.root.take() {
+            self.pending_dir = Some(self.traversal.root(&root));
+        }
+
+        if let Some(pending) = self.pending_dir.take() {
+            let entries = read_dir_entries(&pending.path, self.traversal.filter.sorted)?;
+            let ignore = self.traversal.ignore_rules(&pending);
+
+            self.stack.push(Frame {
+                path: pending.path,
+                entries,
+                depth: pending.depth,
+                id: pending.id,
+                ignore,
+            });
+        }
+        Ok(())
+    }
+
+    // Produz a próxima entrada, identificando o caminho que causou cada falha
+    fn next_entry(&mut self) -> Option<Result<FileInfo, Failure>> {
+        if let Err(e) = self.open_pending() {
+            return Some(Err(e));
+        }
+
+        loop {
+            let frame = self.stack.last_mut()?;
+            let depth = frame.depth;
+            let path = match frame.entries.next() {
+                Some(Ok(path)) => path,
+                Some(Err(e)) => return Some(Err((frame.path.clone(), e.into()))),
+                None => {
+                    self.stack.pop();
+                    continue;
+                }
+            };
+
+            let file_info = match self.traversal.read_entry(path, depth) {
+                Ok(file_info) => file_info,
+                Err(e) => return Some(Err(e)),
+            };
+
+            let stack = &self.stack;
+            let ignore = stack.last().and_then(|frame| frame.ignore.as_ref());
+            if self.traversal.is_ignored(ignore.map(Arc::as_ref), &file_info) {
+                continue;
+            }
+
+            // O diretório é aberto só na próxima chamada, depois de ser produzido
+            self.pending_dir = self.traversal.descend_into(&file_info, ignore, |id| {
+                stack.iter().any(|frame| frame.id.as_ref() == Some(id))
+            });
+
+            if self.traversal.filter.matches(&file_info) {
+                return Some(Ok(file_info));
+            }
+
+            if let Err(e) = self.open_pending() {
+                return Some(Err(e));
+            }
+        }
+    }
+
+    /// Percorre toda a árvore sem interromper em falhas de entradas individuais
+    ///
+    /// Cada entrada encontrada é passada para `f`; as falhas são devolvidas
+    /// com o caminho que as causou. Apenas uma falha ao abrir a própria raiz
+    /// é tratada como erro.
+    pub(crate) fn for_each_partial<F: FnMut(FileInfo)>(mut self, f: F) -> Result<Vec<Failure>, ArchivusError> {
+        self.open_pending().map_err(|(_, e)| e)?;
+        Ok(drain_partial(std::iter::from_fn(|| self.next_entry()), f))
+    }
+}
+
+impl Iterator for Walker {
+    type Item = Result<FileInfo, ArchivusError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_entry().map(|item| item.map_err(|(_, e)| e))
+    }
+}
+
+// Consome as entradas restantes, separando resultados e falhas
+pub(crate) fn drain_partial<I, F>(entries: I, mut f: F) -> Vec<Failure>
+where
+    I: Iterator<Item = Result<FileInfo, Failure>>,
+    F: FnMut(FileInfo),
+{
+    let mut failures = Vec::new();
+    for item in entries {
+        match item {
+            Ok(file_info) => f(file_info),
+            Err(failure) => failures.push(failure),
+        }
+    }
+    failures
+}
+
+/// Percurso sequencial ou paralelo, escolhido por `FileFilter::threads`
+pub(crate) enum Entries {
+    Sequential(Walker),
+    Parallel(ParallelWalker),
+}
+
+impl Entries {
+    pub(crate) fn new<P: AsRef<Path>>(root: P, filter: &FileFilter) -> Self {
+        if filter.threads == 1 {
+            Entries::Sequential(Walker::new(root, filter))
+        } else {
+            Entries::Parallel(ParallelWalker::new(root, filter))
+        }
+    }
+
+    pub(crate) fn for_each_partial<F: FnMut(FileInfo)>(self, f: F) -> Result<Vec<Failure>, ArchivusError> {
+        match self {
+            Entries::Sequential(walker) => walker.for_each_partial(f),
+            Entries::Parallel(walker) => walker.for_each_partial(f),
+        }
+    }
+}
+
+impl Iterator for Entries {
+    type Item = Result<FileInfo, ArchivusError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Entries::Sequential(walker) => walker.next(),
+            Entries::Parallel(walker) => walker.next(),
+        }
+    }
+}
+
+// ====================================================================
+// TESTES UNITÁRIOS
+// ====================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::scratch_dir;
+
+    #[test]
+    fn test_walker_recursive() {
+        let root = scratch_dir("walker_recursive");
+        fs::create_dir_all(root.join("a/b")).unwrap();
+        fs::write(root.join("um.txt"), "1").unwrap();
+        fs::write(root.join("a/dois.rs"), "22").unwrap();
+        fs::write(root.join("a/b/tres.txt"), "333").unwrap();
+
+        let filter = FileFilter {
+            recursive: true,
+            ..Default::default()
+        };
+        let mut names: Vec<String> = Walker::new(&root, &filter)
+            .map(|r| r.unwrap().name)
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["a", "b", "dois.rs", "tres.txt", "um.txt"]);
+
+        let filter = FileFilter {
+            extensions: Some(vec!["txt".to_string()]),
+            include_directories: false,
+            recursive: true,
+            ..Default::default()
+        };
+        assert_eq!(Walker::new(&root, &filter).count(), 2);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_walker_stops_early() {
+        let root = scratch_dir("walker_early");
+        fs::create_dir_all(root.join("sub")).unwrap();
+
+        let filter = FileFilter {
+            recursive: true,
+            ..Default::default()
+        };
+        let mut walker = Walker::new(&root, &filter);
+        let first = walker.next().unwrap().unwrap();
+        assert!(first.is_directory);
+
+        // O subdiretório é removido antes de ser aberto: o erro só aparece
+        // porque a leitura é feita sob demanda
+        fs::remove_dir(root.join("sub")).unwrap();
+        assert!(matches!(walker.next(), Some(Err(ArchivusError::NotFound(_)))));
+        assert!(walker.next().is_none());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_walker_depth_limits() {
+        let root = scratch_dir("walker_depth");
+        fs::create_dir_all(root.join("a/b/c")).unwrap();
+        fs::write(root.join("a/b/c/fundo.txt"), "x").unwrap();
+
+        let filter = FileFilter {
+            recursive: true,
+            min_depth: Some(2),
+            max_depth: Some(3),
+            ..Default::default()
+        };
+        let found: Vec<(String, usize)> = Walker::new(&root, &filter)
+            .map(|r| r.unwrap())
+            .map(|f| (f.name, f.depth))
+            .collect();
+        assert_eq!(found, vec![("b".to_string(), 2), ("c".to_string(), 3)]);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_walker_symlink_policies() {
+        use std::os::unix::fs::symlink;
+
+        let root = scratch_dir("walker_symlinks");
+        let outside = scratch_dir("walker_symlinks_outside");
+        fs::create_dir_all(root.join("a")).unwrap();
+        fs::write(outside.join("fora.txt"), "x").unwrap();
+        fs::write(root.join("a/dentro.txt"), "abc").unwrap();
+        symlink("..", root.join("a/ciclo")).unwrap();
+        symlink(&outside, root.join("externo")).unwrap();
+        symlink(outside.join("fora.txt"), root.join("arquivo_externo")).unwrap();
+        symlink("a/dentro.txt", root.join("arquivo_interno")).unwrap();
+
+        let names = |symlinks| {
+            let filter = FileFilter {
+                recursive: true,
+                symlinks,
+                ..Default::default()
+            };
+            let mut names: Vec<String> = Walker::new(&root, &filter)
+                .map(|r| r.unwrap().name)
+                .collect();
+            names.sort();
+            names
+        };
+
+        // O ciclo a/ciclo -> raiz é reportado, mas não percorrido novamente
+        let all = ["a", "arquivo_externo", "arquivo_interno", "ciclo", "dentro.txt", "externo"];
+        assert_eq!(names(SymlinkPolicy::Follow), [&all[..], &["fora.txt"]].concat());
+        assert_eq!(names(SymlinkPolicy::FollowWithinRoot), all);
+        assert_eq!(names(SymlinkPolicy::NoFollow), all);
+
+        // Um link para um arquivo fora da raiz também não é seguido
+        let filter = FileFilter { symlinks: SymlinkPolicy::FollowWithinRoot, ..Default::default() };
+        let found: Vec<FileInfo> = Walker::new(&root, &filter).map(|r| r.unwrap()).collect();
+        let entry = |name: &str| found.iter().find(|f| f.name == name).unwrap();
+        assert!(entry("arquivo_externo").is_symlink && !entry("arquivo_externo").is_file);
+        assert!(entry("arquivo_interno").is_file);
+        assert_eq!(entry("arquivo_interno").size, 3);
+
+        let link = FileInfo::new_no_follow(root.join("a/ciclo")).unwrap();
+        assert!(link.is_symlink && !link.is_directory);
+        assert_eq!(link.link_target, Some(PathBuf::from("..")));
+        assert!(FileInfo::new(root.join("a/ciclo")).unwrap().is_directory);
+
+        fs::remove_dir_all(&root).unwrap();
+        fs::remove_dir_all(&outside).unwrap();
+    }
+
+    #[test]
+    fn test_walker_partial_collects_failures() {
+        let root = scratch_dir("walker_partial");
+        fs::create_dir_all(root.join("sumiu")).unwrap();
+        fs::create_dir_all(root.join("fica")).unwrap();
+        fs::write(root.join("fica/dado.txt"), "x").unwrap();
+
+        let filter = FileFilter {
+            recursive: true,
+            ..Default::default()
+        };
+        let mut names = Vec::new();
+        let failures = Walker::new(&root, &filter)
+            .for_each_partial(|info| {
+                // Simula um diretório apagado durante a varredura
+                if info.name == "sumiu" {
+                    fs::remove_dir(&info.path).unwrap();
+                }
+                names.push(info.name);
+            })
+            .unwrap();
+        names.sort();
+
+        assert_eq!(names, vec!["dado.txt", "fica", "sumiu"]);
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].0, root.join("sumiu"));
+        assert!(matches!(failures[0].1, ArchivusError::NotFound(_)));
+
+        assert!(Walker::new(root.join("inexistente"), &filter).for_each_partial(|_| {}).is_err());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_walker_ignore_files() {
+        let root = scratch_dir("walker_ignore");
+        fs::create_dir_all(root.join("target/debug")).unwrap();
+        fs::create_dir_all(root.join("src/gerado")).unwrap();
+        fs::create_dir_all(root.join("vendor")).unwrap();
+        fs::write(root.join(".gitignore"), "target/\n*.log\n!manter.log\n").unwrap();
+        fs::write(root.join("src/.ignore"), "*.rs\n").unwrap();
+        fs::write(root.join("src/.archivusignore"), "!main.rs\n/gerado\n").unwrap();
+        for file in ["target/debug/app", "erro.log", "manter.log", "src/main.rs", "src/lib.rs", "src/gerado/x.rs", "vendor/dep.rs"] {
+            fs::write(root.join(file), "x").unwrap();
+        }
+
+        let filter = FileFilter {
+            recursive: true,
+            include_directories: false,
+            respect_ignore_files: true,
+            global_ignore: Some(vec!["vendor/".to_string()]),
+            sorted: true,
+            ..Default::default()
+        };
+        let names: Vec<String> = Walker::new(&root, &filter)
+            .map(|r| r.unwrap().path.strip_prefix(&root).unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, vec![".gitignore", "manter.log", "src/.archivusignore", "src/.ignore", "src/main.rs"]);
+
+        let parallel = crate::ParallelWalker::new(&root, &FileFilter { threads: 2, ..filter });
+        assert_eq!(parallel.count(), names.len());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_walker_missing_root() {
+        let mut walker = Walker::new("diretorio_inexistente_walker", &FileFilter::default());
+        assert!(matches!(walker.next(), Some(Err(ArchivusError::NotFound(_)))));
+        assert!(walker.next().is_none());
+    }
+}